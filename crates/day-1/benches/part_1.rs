@@ -0,0 +1,74 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::SmallRng, seq::IndexedRandom, RngExt, SeedableRng};
+
+/// Part 1 as it was before the byte-level scan, decoding every line twice.
+fn solve_part_1_chars(input: &str) -> u32 {
+    let mut result = 0;
+
+    for line in input.lines() {
+        let first_digit = line
+            .chars()
+            .find(|c| c.is_ascii_digit())
+            .expect("number is always present in input line")
+            .to_digit(10)
+            .expect("valid digit passed");
+        let last_digit = line
+            .chars()
+            .rev()
+            .find(|c| c.is_ascii_digit())
+            .expect("number is always present in input line")
+            .to_digit(10)
+            .expect("valid digit passed");
+
+        result += first_digit * 10 + last_digit;
+    }
+
+    result
+}
+
+/// Builds a calibration document of roughly `size` bytes out of `alphabet`,
+/// with a digit somewhere in every line.
+fn generate(size: usize, alphabet: &[char]) -> String {
+    let mut rng = SmallRng::seed_from_u64(1);
+
+    let mut input = String::with_capacity(size + 64);
+    while input.len() < size {
+        let length = rng.random_range(8..56);
+        let digit = rng.random_range(0..length);
+        for i in 0..length {
+            if i == digit {
+                input.push(rng.random_range('1'..='9'));
+            } else {
+                input.push(*alphabet.choose(&mut rng).unwrap());
+            }
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+fn part_1(c: &mut Criterion) {
+    let ascii = ('a'..='z').chain('0'..='9').collect::<Vec<_>>();
+    let mixed = ('a'..='z')
+        .chain(['ä', 'ß', 'é', '€', '😀', '7'])
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("part_1");
+    for (name, alphabet) in [("ascii", &ascii), ("non-ascii", &mixed)] {
+        let input = generate(4 << 20, alphabet);
+        assert_eq!(day_1::solve_part_1(&input), solve_part_1_chars(&input));
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("chars", name), &input, |b, input| {
+            b.iter(|| solve_part_1_chars(input))
+        });
+        group.bench_with_input(BenchmarkId::new("bytes", name), &input, |b, input| {
+            b.iter(|| day_1::solve_part_1(input))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, part_1);
+criterion_main!(benches);
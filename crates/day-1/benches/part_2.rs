@@ -0,0 +1,57 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_1::Vocabulary;
+use rand::{rngs::SmallRng, seq::IndexedRandom, RngExt, SeedableRng};
+
+/// Builds a calibration document of roughly `size` bytes mixing the words of
+/// `vocabulary` with filler letters, with a digit somewhere in every line.
+fn generate(size: usize, vocabulary: &Vocabulary) -> String {
+    let words = vocabulary.words().map(|(word, _)| word).collect::<Vec<_>>();
+    let mut rng = SmallRng::seed_from_u64(1);
+
+    let mut input = String::with_capacity(size + 64);
+    while input.len() < size {
+        let length = rng.random_range(4..20);
+        let digit = rng.random_range(0..length);
+        for i in 0..length {
+            if i == digit {
+                input.push(rng.random_range('1'..='9'));
+            } else if rng.random_ratio(1, 3) {
+                input.push_str(words.choose(&mut rng).unwrap());
+            } else {
+                input.push(rng.random_range('a'..='z'));
+            }
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+fn part_2(c: &mut Criterion) {
+    let german = "eins = 1\nzwei = 2\ndrei = 3\nvier = 4\nfünf = 5\nsechs = 6\nsieben = 7\nacht = 8\nneun = 9"
+        .parse::<Vocabulary>()
+        .unwrap();
+    let english = Vocabulary::english();
+    let both = Vocabulary::english().merge(german);
+
+    let mut group = c.benchmark_group("part_2");
+    for (name, vocabulary) in [("english", &english), ("english and german", &both)] {
+        let input = generate(1 << 20, vocabulary);
+        assert_eq!(
+            day_1::solve_part_2(&input, vocabulary),
+            day_1::solve_part_2_substrings(&input, vocabulary)
+        );
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("substrings", name), &input, |b, input| {
+            b.iter(|| day_1::solve_part_2_substrings(input, vocabulary))
+        });
+        group.bench_with_input(BenchmarkId::new("graph", name), &input, |b, input| {
+            b.iter(|| day_1::solve_part_2(input, vocabulary))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, part_2);
+criterion_main!(benches);
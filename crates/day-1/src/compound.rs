@@ -0,0 +1,378 @@
+use std::{cmp::Reverse, error::Error, fmt::Display, iter::Peekable, ops::Range};
+
+use crate::Graph;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberWord {
+    /// `zero` to `nineteen`.
+    Unit(u64),
+    /// `twenty` to `ninety`.
+    Tens(u64),
+    Hundred,
+    /// `thousand` and up.
+    Scale(u64),
+}
+
+/// Number read from a line, spanning one or more words or a run of digits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Number {
+    /// Byte range of the number in the line.
+    pub span: Range<usize>,
+    pub value: u64,
+}
+
+/// Words of a number being read, e.g. `one hundred and` so far.
+struct Builder {
+    span: Range<usize>,
+    total: u64,
+    current: u64,
+    last: NumberWord,
+    scale: u64,
+}
+
+impl Builder {
+    /// Starts a number with `word`. A number can't start with `hundred` or a
+    /// scale word.
+    fn start(word: NumberWord, span: Range<usize>) -> Option<Self> {
+        let current = match word {
+            NumberWord::Unit(value) | NumberWord::Tens(value) => value,
+            NumberWord::Hundred | NumberWord::Scale(_) => return None,
+        };
+
+        Some(Self {
+            span,
+            total: 0,
+            current,
+            last: word,
+            scale: u64::MAX,
+        })
+    }
+
+    /// Extends the number with `word` if it continues it, e.g. `three` after
+    /// `twenty`. `separator` is the text between the number and the word.
+    fn push(&mut self, word: NumberWord, span: Range<usize>, separator: &str) -> bool {
+        let after_hundred_or_scale =
+            matches!(self.last, NumberWord::Hundred | NumberWord::Scale(_));
+        match separator {
+            "" | " " | "-" => (),
+            " and " if after_hundred_or_scale => (),
+            _ => return false,
+        }
+
+        let is_leading_zero = self.last == NumberWord::Unit(0);
+        match (self.last, word) {
+            (NumberWord::Tens(_), NumberWord::Unit(value)) if (1..=9).contains(&value) => {
+                self.current += value
+            }
+            (NumberWord::Hundred, NumberWord::Unit(value)) if value != 0 => self.current += value,
+            (NumberWord::Hundred, NumberWord::Tens(value)) => self.current += value,
+            (NumberWord::Scale(_), NumberWord::Unit(value)) if value != 0 => self.current = value,
+            (NumberWord::Scale(_), NumberWord::Tens(value)) => self.current = value,
+            (NumberWord::Unit(_) | NumberWord::Tens(_), NumberWord::Hundred)
+                if !is_leading_zero && self.current < 100 =>
+            {
+                self.current *= 100
+            }
+            (
+                NumberWord::Unit(_) | NumberWord::Tens(_) | NumberWord::Hundred,
+                NumberWord::Scale(scale),
+            ) if !is_leading_zero && scale < self.scale => {
+                self.total += self.current * scale;
+                self.current = 0;
+                self.scale = scale;
+            }
+            _ => return false,
+        }
+
+        self.last = word;
+        self.span.end = span.end;
+        true
+    }
+
+    fn finish(self) -> Number {
+        Number {
+            span: self.span,
+            value: self.total + self.current,
+        }
+    }
+}
+
+/// Reads numbers written with English number words, like `twenty-three` or
+/// `one hundred and five`, or with digits.
+pub struct NumberReader {
+    graph: Graph<NumberWord>,
+}
+
+impl NumberReader {
+    pub fn english() -> Self {
+        Self {
+            graph: Graph::new([
+                ("zero", NumberWord::Unit(0)),
+                ("one", NumberWord::Unit(1)),
+                ("two", NumberWord::Unit(2)),
+                ("three", NumberWord::Unit(3)),
+                ("four", NumberWord::Unit(4)),
+                ("five", NumberWord::Unit(5)),
+                ("six", NumberWord::Unit(6)),
+                ("seven", NumberWord::Unit(7)),
+                ("eight", NumberWord::Unit(8)),
+                ("nine", NumberWord::Unit(9)),
+                ("ten", NumberWord::Unit(10)),
+                ("eleven", NumberWord::Unit(11)),
+                ("twelve", NumberWord::Unit(12)),
+                ("thirteen", NumberWord::Unit(13)),
+                ("fourteen", NumberWord::Unit(14)),
+                ("fifteen", NumberWord::Unit(15)),
+                ("sixteen", NumberWord::Unit(16)),
+                ("seventeen", NumberWord::Unit(17)),
+                ("eighteen", NumberWord::Unit(18)),
+                ("nineteen", NumberWord::Unit(19)),
+                ("twenty", NumberWord::Tens(20)),
+                ("thirty", NumberWord::Tens(30)),
+                ("forty", NumberWord::Tens(40)),
+                ("fifty", NumberWord::Tens(50)),
+                ("sixty", NumberWord::Tens(60)),
+                ("seventy", NumberWord::Tens(70)),
+                ("eighty", NumberWord::Tens(80)),
+                ("ninety", NumberWord::Tens(90)),
+                ("hundred", NumberWord::Hundred),
+                ("thousand", NumberWord::Scale(1_000)),
+                ("million", NumberWord::Scale(1_000_000)),
+                ("billion", NumberWord::Scale(1_000_000_000)),
+            ]),
+        }
+    }
+
+    /// Returns the numbers in `line`, ignoring case, or `None` if a run of
+    /// digits doesn't fit in a `u64`. Words that overlap are read
+    /// leftmost-longest, so `eighteen` is never read as `eight`.
+    pub fn numbers(&self, line: &str) -> Option<Vec<Number>> {
+        let line = line.to_ascii_lowercase();
+
+        let mut words = self.graph.find_iter(&line).collect::<Vec<_>>();
+        words.sort_by_key(|m| (m.start, Reverse(m.end)));
+
+        let mut numbers = Vec::new();
+        let mut builder: Option<Builder> = None;
+        let mut digits = line
+            .char_indices()
+            .filter(|(_, c)| c.is_ascii_digit())
+            .peekable();
+        let mut end = 0;
+
+        for word in words {
+            if word.start < end {
+                continue;
+            }
+            end = word.end;
+
+            while let Some((start, _)) = digits.next_if(|(index, _)| *index < word.start) {
+                numbers.extend(builder.take().map(Builder::finish));
+                numbers.push(read_digits(&line, start, &mut digits)?);
+            }
+
+            if let Some(b) = &mut builder {
+                let separator = &line[b.span.end..word.start];
+                if b.push(word.value, word.start..word.end, separator) {
+                    continue;
+                }
+            }
+
+            numbers.extend(builder.take().map(Builder::finish));
+            builder = Builder::start(word.value, word.start..word.end);
+        }
+        numbers.extend(builder.take().map(Builder::finish));
+
+        while let Some((start, _)) = digits.next() {
+            numbers.push(read_digits(&line, start, &mut digits)?);
+        }
+
+        Some(numbers)
+    }
+}
+
+/// Reads the run of digits starting at `start`, consuming its remaining digits
+/// from `digits`, or returns `None` if it doesn't fit in a `u64`.
+fn read_digits(
+    line: &str,
+    start: usize,
+    digits: &mut Peekable<impl Iterator<Item = (usize, char)>>,
+) -> Option<Number> {
+    let mut end = start + 1;
+    while digits.next_if(|(index, _)| *index == end).is_some() {
+        end += 1;
+    }
+
+    Some(Number {
+        span: start..end,
+        value: line[start..end].parse().ok()?,
+    })
+}
+
+/// Why a line of the compound calibration document has no value. Lines are
+/// 1-based.
+#[derive(Debug, PartialEq)]
+pub enum CompoundError {
+    /// A number, calibration value or sum too large for a `u64`.
+    Overflow { line: usize },
+    /// A line without any number.
+    MissingNumber { line: usize },
+}
+
+impl Display for CompoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompoundError::Overflow { line } => write!(f, "line {line}: number too large"),
+            CompoundError::MissingNumber { line } => write!(f, "line {line}: no number"),
+        }
+    }
+}
+
+impl Error for CompoundError {}
+
+/// Calibration value of the line with `first` and `last` as its first and
+/// last numbers, `None` if it doesn't fit in a `u64`.
+fn calibration_value(first: u64, last: u64) -> Option<u64> {
+    if first == 0 {
+        return Some(last);
+    }
+    let shift = 10u64.checked_pow(last.checked_ilog10().unwrap_or(0) + 1)?;
+    first.checked_mul(shift)?.checked_add(last)
+}
+
+/// Sums the calibration values of `input`, where a line's value is its first
+/// number followed by the digits of its last number.
+pub fn solve_compound(input: &str) -> Result<u64, CompoundError> {
+    let reader = NumberReader::english();
+    let mut result = 0u64;
+
+    for (index, line) in input.lines().enumerate() {
+        let numbers = reader
+            .numbers(line)
+            .ok_or(CompoundError::Overflow { line: index + 1 })?;
+        let (Some(first), Some(last)) = (numbers.first(), numbers.last()) else {
+            return Err(CompoundError::MissingNumber { line: index + 1 });
+        };
+
+        result = calibration_value(first.value, last.value)
+            .and_then(|value| result.checked_add(value))
+            .ok_or(CompoundError::Overflow { line: index + 1 })?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(line: &str) -> Vec<u64> {
+        NumberReader::english()
+            .numbers(line)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.value)
+            .collect()
+    }
+
+    #[test]
+    fn compound_words() {
+        assert_eq!(values("eleven"), [11]);
+        assert_eq!(values("twenty"), [20]);
+        assert_eq!(values("twenty-three"), [23]);
+        assert_eq!(values("xtwentythreex"), [23]);
+        assert_eq!(values("one hundred"), [100]);
+        assert_eq!(values("one hundred and five"), [105]);
+        assert_eq!(values("nineteen hundred eighty-four"), [1984]);
+        assert_eq!(values("two thousand twenty-four"), [2024]);
+        assert_eq!(values("three million two hundred thousand"), [3_200_000]);
+        assert_eq!(values("Forty-Two"), [42]);
+    }
+
+    #[test]
+    fn separate_numbers() {
+        assert_eq!(values("one two"), [1, 2]);
+        assert_eq!(values("twenty twenty"), [20, 20]);
+        assert_eq!(values("seventy7"), [70, 7]);
+        assert_eq!(values("one thousand one thousand"), [1001]);
+        assert_eq!(values("ten and five"), [10, 5]);
+        assert_eq!(values("zero hundred"), [0]);
+        assert_eq!(values("hundred"), []);
+        assert_eq!(values("eightwo"), [8]);
+    }
+
+    #[test]
+    fn spans() {
+        let numbers = NumberReader::english()
+            .numbers("a twenty-one b 34 c one hundred")
+            .unwrap();
+
+        assert_eq!(
+            numbers,
+            [
+                Number {
+                    span: 2..12,
+                    value: 21
+                },
+                Number {
+                    span: 15..17,
+                    value: 34
+                },
+                Number {
+                    span: 20..31,
+                    value: 100
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn calibration() {
+        let input = "one hundred and five apples, 7 pears, twenty
+two1nine
+thirteen";
+
+        assert_eq!(solve_compound(input), Ok(10520 + 29 + 1313));
+    }
+
+    #[test]
+    fn missing_numbers() {
+        assert_eq!(
+            solve_compound("one\n\nxyz"),
+            Err(CompoundError::MissingNumber { line: 2 })
+        );
+        assert_eq!(
+            solve_compound("one\nhundred"),
+            Err(CompoundError::MissingNumber { line: 2 })
+        );
+        assert_eq!(
+            CompoundError::MissingNumber { line: 3 }.to_string(),
+            "line 3: no number"
+        );
+    }
+
+    #[test]
+    fn large_numbers() {
+        let max = u64::MAX.to_string();
+        assert_eq!(values(&max), [u64::MAX]);
+        let reader = NumberReader::english();
+        assert_eq!(reader.numbers(&format!("a {max}0 b")), None);
+        assert_eq!(reader.numbers("99999999999999999999"), None);
+
+        // u64::MAX split into its first digit and the rest.
+        assert_eq!(solve_compound("1 8446744073709551615"), Ok(u64::MAX));
+        assert_eq!(
+            solve_compound("one\n1 8446744073709551616"),
+            Err(CompoundError::Overflow { line: 2 })
+        );
+        assert_eq!(
+            solve_compound("nine billion and 99999999999"),
+            Err(CompoundError::Overflow { line: 1 })
+        );
+        assert_eq!(
+            solve_compound(&format!("1\n{max}")),
+            Err(CompoundError::Overflow { line: 2 })
+        );
+        assert_eq!(solve_compound(&format!("zero\n0 {max}")), Ok(u64::MAX));
+    }
+}
@@ -1,201 +1,546 @@
-use std::collections::HashMap;
-
-#[derive(Debug, PartialEq)]
-struct NodeIndex(usize);
-
-#[derive(Debug, PartialEq)]
-struct MapIndex(usize);
+mod compound;
+mod scan;
+
+pub use compound::{solve_compound, CompoundError, Number, NumberReader};
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::Display,
+    str::{CharIndices, FromStr},
+};
+
+/// Occurrence of one of the graph's keys in a haystack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<T> {
+    /// Byte offset of the first byte of the key.
+    pub start: usize,
+    /// Byte offset just past the last byte of the key.
+    pub end: usize,
+    pub value: T,
+}
 
-type NodeMap = HashMap<char, NodeIndex>;
+#[derive(Debug)]
+struct Node {
+    next: HashMap<char, usize>,
+    fail: usize,
+    /// Keys ending at this node, longest first, including the ones reached
+    /// through the failure links.
+    keys: Vec<usize>,
+}
 
-#[derive(Debug, PartialEq)]
-enum Node {
-    Returnable(u32),
-    Intermediate(MapIndex),
+impl Node {
+    fn new() -> Self {
+        Self {
+            next: HashMap::new(),
+            fail: 0,
+            keys: Vec::new(),
+        }
+    }
 }
 
+/// Aho–Corasick automaton over a dictionary of keys mapped to values.
 #[derive(Debug)]
-struct Graph {
-    maps: Vec<NodeMap>,
+pub struct Graph<T = u32> {
+    keys: Vec<(String, T)>,
     nodes: Vec<Node>,
 }
 
-impl Graph {
-    fn new() -> Self {
-        Self {
-            maps: Vec::new(),
-            nodes: Vec::new(),
-        }
-    }
-
-    pub fn forward() -> Self {
-        let mut graph = Self::new();
-
-        graph.add_key("one", 1);
-        graph.add_key("two", 2);
-        graph.add_key("three", 3);
-        graph.add_key("four", 4);
-        graph.add_key("five", 5);
-        graph.add_key("six", 6);
-        graph.add_key("seven", 7);
-        graph.add_key("eight", 8);
-        graph.add_key("nine", 9);
-        graph.add_key("1", 1);
-        graph.add_key("2", 2);
-        graph.add_key("3", 3);
-        graph.add_key("4", 4);
-        graph.add_key("5", 5);
-        graph.add_key("6", 6);
-        graph.add_key("7", 7);
-        graph.add_key("8", 8);
-        graph.add_key("9", 9);
+impl<T: Clone> Graph<T> {
+    /// Builds the automaton for `keys`. Empty keys are ignored.
+    pub fn new<K: AsRef<str>>(keys: impl IntoIterator<Item = (K, T)>) -> Self {
+        let mut graph = Self {
+            keys: Vec::new(),
+            nodes: vec![Node::new()],
+        };
+
+        for (key, value) in keys {
+            graph.add_key(key.as_ref(), value);
+        }
+        graph.link();
 
         graph
     }
 
-    pub fn backward() -> Self {
-        let mut graph = Self::new();
-
-        graph.add_key("eno", 1);
-        graph.add_key("owt", 2);
-        graph.add_key("eerht", 3);
-        graph.add_key("ruof", 4);
-        graph.add_key("evif", 5);
-        graph.add_key("xis", 6);
-        graph.add_key("neves", 7);
-        graph.add_key("thgie", 8);
-        graph.add_key("enin", 9);
-        graph.add_key("1", 1);
-        graph.add_key("2", 2);
-        graph.add_key("3", 3);
-        graph.add_key("4", 4);
-        graph.add_key("5", 5);
-        graph.add_key("6", 6);
-        graph.add_key("7", 7);
-        graph.add_key("8", 8);
-        graph.add_key("9", 9);
+    /// Graph over the same dictionary with every key reversed, for scanning a
+    /// haystack from its end.
+    pub fn reversed(&self) -> Self {
+        Self::new(
+            self.keys
+                .iter()
+                .map(|(key, value)| (key.chars().rev().collect::<String>(), value.clone())),
+        )
+    }
 
-        graph
+    /// Iterates over every occurrence of every key in `haystack`, overlapping
+    /// ones included, ordered by where they end.
+    pub fn find_iter<'g, 'h>(&'g self, haystack: &'h str) -> Matches<'g, 'h, T> {
+        Matches {
+            graph: self,
+            chars: haystack.char_indices(),
+            node: 0,
+            end: 0,
+            pending: 0,
+        }
     }
 
-    fn add_key(&mut self, key: &str, returnable: u32) {
+    fn add_key(&mut self, key: &str, value: T) {
         if key.is_empty() {
             return;
         }
-        let len = key.len();
 
         let mut head = 0;
-        for (index, char) in key.chars().enumerate() {
-            let mut map_count = self.maps.len();
-            let map = match self.maps.get_mut(head) {
-                Some(map) => map,
+        for char in key.chars() {
+            head = match self.nodes[head].next.get(&char) {
+                Some(next) => *next,
                 None => {
-                    self.maps.push(HashMap::new());
-                    map_count += 1;
-                    self.maps.get_mut(head).expect("just added")
+                    self.nodes.push(Node::new());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[head].next.insert(char, next);
+                    next
                 }
             };
+        }
 
-            match map.get(&char) {
-                Some(node_index) => match self.nodes.get(node_index.0).expect("must exist") {
-                    Node::Intermediate(map_index) => head = map_index.0,
-                    Node::Returnable(_) => unreachable!(),
-                },
-                None => {
-                    let node = if index == len - 1 {
-                        Node::Returnable(returnable)
-                    } else {
-                        head = map_count;
-                        Node::Intermediate(MapIndex(head))
-                    };
-                    self.nodes.push(node);
-
-                    map.insert(char, NodeIndex(self.nodes.len() - 1));
-                }
+        self.nodes[head].keys.push(self.keys.len());
+        self.keys.push((key.to_string(), value));
+    }
+
+    /// Computes the failure links breadth first, so every node's link points
+    /// to an already finished node.
+    fn link(&mut self) {
+        let mut queue = self.nodes[0]
+            .next
+            .values()
+            .copied()
+            .collect::<VecDeque<_>>();
+
+        while let Some(head) = queue.pop_front() {
+            let children = self.nodes[head]
+                .next
+                .iter()
+                .map(|(char, next)| (*char, *next))
+                .collect::<Vec<_>>();
+
+            for (char, next) in children {
+                let mut fail = self.nodes[head].fail;
+                let fail = loop {
+                    match self.nodes[fail].next.get(&char) {
+                        Some(node) => break *node,
+                        None if fail == 0 => break 0,
+                        None => fail = self.nodes[fail].fail,
+                    }
+                };
+
+                let mut inherited = self.nodes[fail].keys.clone();
+                let node = &mut self.nodes[next];
+                node.fail = fail;
+                node.keys.append(&mut inherited);
+
+                queue.push_back(next);
             }
         }
     }
 
-    fn parse(&mut self, chars: impl Iterator<Item = char>) -> Option<u32> {
-        let mut heads = Vec::new();
-
-        for c in chars {
-            heads.push(0);
-            let mut offset = 0;
+    fn step(&self, mut node: usize, char: char) -> usize {
+        loop {
+            match self.nodes[node].next.get(&char) {
+                Some(next) => return *next,
+                None if node == 0 => return 0,
+                None => node = self.nodes[node].fail,
+            }
+        }
+    }
 
-            for head_index in 0..heads.len() {
-                let head_index = head_index - offset;
+    /// Returns the value of the first key to be completed by `chars`.
+    fn parse(&self, chars: impl Iterator<Item = char>) -> Option<&T> {
+        let mut node = 0;
 
-                if let Some(node) = self.get_node(c, heads[head_index]) {
-                    match node {
-                        Node::Returnable(result) => return Some(*result),
-                        Node::Intermediate(map_index) => {
-                            heads[head_index] = map_index.0;
-                        }
-                    }
-                } else {
-                    heads.remove(head_index);
-                    offset += 1;
-                }
+        for char in chars {
+            node = self.step(node, char);
+            if let Some(key) = self.nodes[node].keys.first() {
+                return Some(&self.keys[*key].1);
             }
         }
 
         None
     }
+}
 
-    fn get_node(&mut self, char: char, index: usize) -> Option<&Node> {
-        let map = self.maps.get(index).expect("always present");
-        Some(&self.nodes[map.get(&char)?.0])
+/// Iterator over the matches of a [`Graph`], see [`Graph::find_iter`].
+pub struct Matches<'g, 'h, T> {
+    graph: &'g Graph<T>,
+    chars: CharIndices<'h>,
+    node: usize,
+    end: usize,
+    pending: usize,
+}
+
+impl<T: Clone> Iterator for Matches<'_, '_, T> {
+    type Item = Match<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(key) = self.graph.nodes[self.node].keys.get(self.pending) {
+                self.pending += 1;
+
+                let (key, value) = &self.graph.keys[*key];
+                return Some(Match {
+                    start: self.end - key.len(),
+                    end: self.end,
+                    value: value.clone(),
+                });
+            }
+
+            let (index, char) = self.chars.next()?;
+            self.node = self.graph.step(self.node, char);
+            self.end = index + char.len_utf8();
+            self.pending = 0;
+        }
     }
 }
 
-pub fn solve_part_1(input: &str) -> u32 {
-    let mut result = 0;
-
-    for line in input.lines() {
-        let first_digit = line
-            .chars()
-            .find(|c| c.is_ascii_digit())
-            .expect("number is always present in input line")
-            .to_digit(10)
-            .expect("valid digit passed");
-        let last_digit = line
-            .chars()
-            .rev()
-            .find(|c| c.is_ascii_digit())
-            .expect("number is always present in input line")
-            .to_digit(10)
-            .expect("valid digit passed");
+/// Words recognised in calibration lines, each mapped to the digit it spells.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ASCII digits `0` to `9`.
+    pub fn digits() -> Self {
+        (0..=9).fold(Self::new(), |vocabulary, digit| {
+            vocabulary.word(digit.to_string(), digit)
+        })
+    }
+
+    /// The ASCII digits `1` to `9` and the English words `one` to `nine`, as in
+    /// the puzzle. A `0` is not a digit here, see [`Vocabulary::digits`].
+    pub fn english() -> Self {
+        (1..=9)
+            .fold(Self::new(), |vocabulary, digit| {
+                vocabulary.word(digit.to_string(), digit)
+            })
+            .word("one", 1)
+            .word("two", 2)
+            .word("three", 3)
+            .word("four", 4)
+            .word("five", 5)
+            .word("six", 6)
+            .word("seven", 7)
+            .word("eight", 8)
+            .word("nine", 9)
+    }
+
+    pub fn word(mut self, word: impl Into<String>, value: u32) -> Self {
+        self.words.push((word.into(), value));
+        self
+    }
 
-        result += first_digit * 10 + last_digit;
+    /// Adds every word of `other`, e.g. to recognise several languages at once.
+    pub fn merge(mut self, other: Vocabulary) -> Self {
+        self.words.extend(other.words);
+        self
     }
 
-    result
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
 }
 
-pub fn solve_part_2(input: &str) -> u32 {
-    let mut result = 0;
+/// Reads a vocabulary with one `word = value` pair per line. Blank lines and
+/// lines starting with `#` are ignored.
+impl FromStr for Vocabulary {
+    type Err = VocabularyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vocabulary = Self::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let number = index + 1;
+            let (word, value) = line
+                .split_once('=')
+                .ok_or(VocabularyError::MissingValue(number))?;
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(VocabularyError::EmptyWord(number));
+            }
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|_| VocabularyError::InvalidValue(number))?;
+
+            vocabulary = vocabulary.word(word, value);
+        }
 
-    let mut forward_graph = Graph::forward();
-    let mut backward_graph = Graph::backward();
+        Ok(vocabulary)
+    }
+}
 
-    for line in input.lines() {
-        let first = forward_graph
-            .parse(line.chars())
-            .expect("number is always present in input line");
-        let last = backward_graph
-            .parse(line.chars().rev())
-            .expect("number is always present in input line");
+/// Error reading a vocabulary, with the 1-based number of the offending line.
+#[derive(Debug, PartialEq)]
+pub enum VocabularyError {
+    MissingValue(usize),
+    EmptyWord(usize),
+    InvalidValue(usize),
+}
 
-        let number = first * 10 + last;
-        result += number;
+impl Display for VocabularyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VocabularyError::MissingValue(line) => {
+                write!(f, "line {line}: expected `word = value`")
+            }
+            VocabularyError::EmptyWord(line) => write!(f, "line {line}: word is empty"),
+            VocabularyError::InvalidValue(line) => write!(f, "line {line}: value is not a number"),
+        }
     }
+}
+
+impl Error for VocabularyError {}
 
-    result
+impl From<&Vocabulary> for Graph {
+    fn from(value: &Vocabulary) -> Self {
+        Self::new(value.words())
+    }
+}
+
+/// Whether a token was spelled out as a word or written with digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Spelled,
+    Numeric,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// Byte range of the token in the line.
+    pub span: std::ops::Range<usize>,
+    pub value: u32,
+    pub kind: TokenKind,
+}
+
+/// How tokens sharing characters are reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlap {
+    /// Every token is reported, e.g. both `eight` and `two` in `eightwo`.
+    Overlapping,
+    /// Tokens are picked from the left, preferring the longest one, and
+    /// never share characters, e.g. only `eight` in `eightwo`.
+    LeftmostLongest,
+}
+
+/// Splits calibration lines into the digit tokens of a vocabulary.
+pub struct Tokenizer {
+    graph: Graph,
+}
+
+impl Tokenizer {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        Self {
+            graph: vocabulary.into(),
+        }
+    }
+
+    /// Returns the tokens found in `line`, ordered by where they start.
+    pub fn tokenize(&self, line: &str, overlap: Overlap) -> Vec<Token> {
+        let mut tokens = self
+            .graph
+            .find_iter(line)
+            .map(|m| Token {
+                kind: if line[m.start..m.end].bytes().all(|b| b.is_ascii_digit()) {
+                    TokenKind::Numeric
+                } else {
+                    TokenKind::Spelled
+                },
+                span: m.start..m.end,
+                value: m.value,
+            })
+            .collect::<Vec<_>>();
+
+        match overlap {
+            Overlap::Overlapping => {
+                tokens.sort_by_key(|t| (t.span.start, t.span.end));
+            }
+            Overlap::LeftmostLongest => {
+                tokens.sort_by_key(|t| (t.span.start, std::cmp::Reverse(t.span.end)));
+
+                let mut end = 0;
+                tokens.retain(|t| {
+                    if t.span.start < end {
+                        return false;
+                    }
+                    end = t.span.end;
+                    true
+                });
+            }
+        }
+
+        tokens
+    }
+}
+
+/// What to do with a line that has no digit in it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingDigits {
+    /// Stop and report the line.
+    Error,
+    /// Leave the line out of the sum.
+    Skip,
+    /// Count the line as a calibration value of zero.
+    Zero,
+}
+
+/// Sum of a calibration document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Calibration {
+    pub sum: u32,
+    /// 1-based numbers of the lines that had no digit, whether they were
+    /// skipped or counted as zero.
+    pub skipped: Vec<usize>,
+}
+
+/// A line without any digit, reported under [`MissingDigits::Error`].
+#[derive(Debug, PartialEq)]
+pub struct MissingDigitsError {
+    /// 1-based number of the line.
+    pub line: usize,
+}
+
+impl Display for MissingDigitsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: no digit found", self.line)
+    }
+}
+
+impl Error for MissingDigitsError {}
+
+fn calibrate<L>(
+    lines: impl Iterator<Item = L>,
+    policy: MissingDigits,
+    mut digits: impl FnMut(L) -> Option<(u32, u32)>,
+) -> Result<Calibration, MissingDigitsError> {
+    let mut calibration = Calibration::default();
+
+    for (index, line) in lines.enumerate() {
+        match (digits(line), policy) {
+            (Some((first, last)), _) => calibration.sum += first * 10 + last,
+            (None, MissingDigits::Error) => return Err(MissingDigitsError { line: index + 1 }),
+            (None, MissingDigits::Skip | MissingDigits::Zero) => {
+                calibration.skipped.push(index + 1)
+            }
+        }
+    }
+
+    Ok(calibration)
+}
+
+/// Sums the values made of the first and last ASCII digit of every line.
+pub fn calibrate_part_1(
+    input: &str,
+    policy: MissingDigits,
+) -> Result<Calibration, MissingDigitsError> {
+    calibrate(scan::lines(input.as_bytes()), policy, |line| {
+        Some((scan::first_digit(line)?, scan::last_digit(line)?))
+    })
+}
+
+/// Sums the values made of the first and last word of `vocabulary` in every
+/// line.
+pub fn calibrate_part_2(
+    input: &str,
+    vocabulary: &Vocabulary,
+    policy: MissingDigits,
+) -> Result<Calibration, MissingDigitsError> {
+    let forward_graph = Graph::from(vocabulary);
+    let backward_graph = forward_graph.reversed();
+
+    calibrate(input.lines(), policy, |line| {
+        let first = forward_graph.parse(line.chars())?;
+        let last = backward_graph.parse(line.chars().rev())?;
+        Some((*first, *last))
+    })
+}
+
+pub fn solve_part_1(input: &str) -> u32 {
+    calibrate_part_1(input, MissingDigits::Error)
+        .expect("number is always present in input line")
+        .sum
+}
+
+pub fn solve_part_2(input: &str, vocabulary: &Vocabulary) -> u32 {
+    calibrate_part_2(input, vocabulary, MissingDigits::Error)
+        .expect("number is always present in input line")
+        .sum
+}
+
+/// Same as [`calibrate_part_2`], but tries every word of `vocabulary` at every
+/// position of a line instead of running the automaton. Kept to cross-check
+/// and benchmark the [`Graph`] against.
+pub fn calibrate_part_2_substrings(
+    input: &str,
+    vocabulary: &Vocabulary,
+    policy: MissingDigits,
+) -> Result<Calibration, MissingDigitsError> {
+    // Like the automaton, picks the longest of the words found at a position,
+    // and the first one added among words spelled the same.
+    let longest = |found: &dyn Fn(&str) -> bool| {
+        vocabulary
+            .words()
+            .filter(|(word, _)| !word.is_empty() && found(word))
+            .min_by_key(|(word, _)| Reverse(word.len()))
+            .map(|(_, value)| value)
+    };
+
+    calibrate(input.lines(), policy, |line| {
+        let first = line.char_indices().find_map(|(start, char)| {
+            longest(&|word| line[..start + char.len_utf8()].ends_with(word))
+        })?;
+        let last = line
+            .char_indices()
+            .rev()
+            .find_map(|(start, _)| longest(&|word| line[start..].starts_with(word)))?;
+        Some((first, last))
+    })
+}
+
+pub fn solve_part_2_substrings(input: &str, vocabulary: &Vocabulary) -> u32 {
+    calibrate_part_2_substrings(input, vocabulary, MissingDigits::Error)
+        .expect("number is always present in input line")
+        .sum
+}
+
+/// A named way of solving a part of the puzzle.
+pub type Solver = (&'static str, fn(&str) -> u64);
+
+/// Every way of solving part 2 with the English vocabulary, by name.
+pub fn strategies() -> &'static [Solver] {
+    &[
+        ("graph", |input| {
+            solve_part_2(input, &Vocabulary::english()).into()
+        }),
+        ("substrings", |input| {
+            solve_part_2_substrings(input, &Vocabulary::english()).into()
+        }),
+    ]
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     #[test]
     fn part_1() {
@@ -207,6 +552,15 @@ treb7uchet";
         assert_eq!(solve_part_1(input), 142);
     }
 
+    #[test]
+    fn part_1_non_ascii() {
+        let input = "ä1ü€2ö
+3ßabcdefghijklmnopqrstu
+naïve 4 café 5 😀";
+
+        assert_eq!(solve_part_1(input), 12 + 33 + 45);
+    }
+
     #[test]
     fn part_2() {
         let input = "two1nine
@@ -217,6 +571,286 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
 
-        assert_eq!(solve_part_2(input), 281);
+        assert_eq!(solve_part_2(input, &Vocabulary::english()), 281);
+    }
+
+    #[test]
+    fn zero_is_not_english() {
+        assert_eq!(solve_part_2("a0b1c", &Vocabulary::english()), 11);
+        assert_eq!(solve_part_2("zero7", &Vocabulary::english()), 77);
+        assert_eq!(
+            calibrate_part_2("0", &Vocabulary::english(), MissingDigits::Error),
+            Err(MissingDigitsError { line: 1 })
+        );
+
+        let vocabulary = Vocabulary::english().merge(Vocabulary::digits());
+        assert_eq!(solve_part_2("a0b1c", &vocabulary), 1);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+ééntwone1é
+sevenine";
+
+        for (name, solve) in strategies() {
+            assert_eq!(solve(input), 281 + 21 + 79, "{name}");
+        }
+    }
+
+    /// Calibration document with a digit in every line, around it letters
+    /// that often spell words, some of them not ASCII.
+    fn document() -> impl Strategy<Value = String> {
+        let line = "[onetwhrfuivsxgné0]{0,12}[1-9][onetwhrfuivsxgné0]{0,12}";
+        proptest::collection::vec(line, 1..5).prop_map(|lines| lines.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn substrings_same_as_graph(input in document()) {
+            // Words nested in others, words spelled twice and non-ASCII ones.
+            let vocabulary = Vocabulary::english()
+                .word("ne", 5)
+                .word("twone", 7)
+                .word("one", 4)
+                .word("één", 6)
+                .word("én", 8);
+
+            prop_assert_eq!(
+                solve_part_2_substrings(&input, &Vocabulary::english()),
+                solve_part_2(&input, &Vocabulary::english())
+            );
+            prop_assert_eq!(
+                solve_part_2_substrings(&input, &vocabulary),
+                solve_part_2(&input, &vocabulary)
+            );
+        }
+    }
+
+    #[test]
+    fn missing_digits() {
+        let input = "1abc2
+
+pqr3stu8vwx
+treb-uchet";
+
+        assert_eq!(
+            calibrate_part_1(input, MissingDigits::Error),
+            Err(MissingDigitsError { line: 2 })
+        );
+        assert_eq!(
+            calibrate_part_1(input, MissingDigits::Skip),
+            Ok(Calibration {
+                sum: 12 + 38,
+                skipped: vec![2, 4]
+            })
+        );
+        assert_eq!(
+            calibrate_part_1(input, MissingDigits::Zero),
+            calibrate_part_1(input, MissingDigits::Skip)
+        );
+        assert_eq!(
+            calibrate_part_2(input, &Vocabulary::english(), MissingDigits::Skip),
+            Ok(Calibration {
+                sum: 12 + 38,
+                skipped: vec![2, 4]
+            })
+        );
+        assert_eq!(
+            calibrate_part_2("two\nabc", &Vocabulary::english(), MissingDigits::Error),
+            Err(MissingDigitsError { line: 2 })
+        );
+        assert_eq!(
+            calibrate_part_2_substrings(input, &Vocabulary::english(), MissingDigits::Zero),
+            calibrate_part_2(input, &Vocabulary::english(), MissingDigits::Zero)
+        );
+        assert_eq!(
+            calibrate_part_2_substrings("two\nabc", &Vocabulary::english(), MissingDigits::Error),
+            Err(MissingDigitsError { line: 2 })
+        );
+        assert_eq!(
+            calibrate_part_1("", MissingDigits::Error),
+            Ok(Calibration::default())
+        );
+    }
+
+    #[test]
+    fn several_languages() {
+        let german = "eins = 1
+zwei = 2
+drei = 3
+vier = 4
+fünf = 5
+sechs = 6
+sieben = 7
+acht = 8
+neun = 9"
+            .parse::<Vocabulary>()
+            .unwrap();
+        let vocabulary = Vocabulary::english().merge(german);
+
+        assert_eq!(solve_part_2("zweins", &vocabulary), 21);
+        assert_eq!(solve_part_2("sechseven", &vocabulary), 67);
+        assert_eq!(solve_part_2("achtwo", &vocabulary), 82);
+        assert_eq!(solve_part_2("xdreightx", &vocabulary), 38);
+        assert_eq!(solve_part_2("einsiebenfünf", &vocabulary), 15);
+        assert_eq!(solve_part_2("einsieben", &vocabulary), 17);
+    }
+
+    #[test]
+    fn custom_words() {
+        let vocabulary = Vocabulary::new()
+            .word("zero", 0)
+            .word("one", 1)
+            .word("i", 1)
+            .word("v", 5)
+            .word("x", 10);
+
+        assert_eq!(solve_part_2("zerone", &vocabulary), 1);
+        assert_eq!(solve_part_2("aav", &vocabulary), 55);
+        assert_eq!(solve_part_2("ix", &vocabulary), 20);
+    }
+
+    #[test]
+    fn tokenize() {
+        let tokenizer = Tokenizer::new(&Vocabulary::english());
+        let spans = |line, overlap| {
+            tokenizer
+                .tokenize(line, overlap)
+                .into_iter()
+                .map(|t| (t.span, t.value))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            spans("eightwothree", Overlap::Overlapping),
+            [(0..5, 8), (4..7, 2), (7..12, 3)]
+        );
+        assert_eq!(
+            spans("eightwothree", Overlap::LeftmostLongest),
+            [(0..5, 8), (7..12, 3)]
+        );
+        assert_eq!(
+            spans("xtwone3four", Overlap::Overlapping),
+            [(1..4, 2), (3..6, 1), (6..7, 3), (7..11, 4)]
+        );
+        assert_eq!(
+            spans("xtwone3four", Overlap::LeftmostLongest),
+            [(1..4, 2), (6..7, 3), (7..11, 4)]
+        );
+        assert_eq!(spans("abc", Overlap::Overlapping), []);
+    }
+
+    #[test]
+    fn tokenize_longest() {
+        let vocabulary = Vocabulary::new()
+            .word("seven", 7)
+            .word("eve", 0)
+            .word("seventy", 70);
+        let tokenizer = Tokenizer::new(&vocabulary);
+        let spans = |line, overlap| {
+            tokenizer
+                .tokenize(line, overlap)
+                .into_iter()
+                .map(|t| (t.span, t.value))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            spans("seventy", Overlap::Overlapping),
+            [(0..5, 7), (0..7, 70), (1..4, 0)]
+        );
+        assert_eq!(spans("seventy", Overlap::LeftmostLongest), [(0..7, 70)]);
+    }
+
+    #[test]
+    fn token_kinds() {
+        let tokenizer = Tokenizer::new(&Vocabulary::english());
+        let kinds = tokenizer
+            .tokenize("1two3", Overlap::Overlapping)
+            .into_iter()
+            .map(|t| t.kind)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [TokenKind::Numeric, TokenKind::Spelled, TokenKind::Numeric]
+        );
+    }
+
+    #[test]
+    fn vocabulary_file() {
+        let vocabulary = "# spelled digits
+zero = 0
+
+one=1"
+            .parse::<Vocabulary>()
+            .unwrap();
+        assert_eq!(vocabulary, Vocabulary::new().word("zero", 0).word("one", 1));
+
+        assert_eq!(
+            "one = 1\ntwo".parse::<Vocabulary>(),
+            Err(VocabularyError::MissingValue(2))
+        );
+        assert_eq!(
+            " = 1".parse::<Vocabulary>(),
+            Err(VocabularyError::EmptyWord(1))
+        );
+        assert_eq!(
+            "one = 1\n\ntwo = II".parse::<Vocabulary>(),
+            Err(VocabularyError::InvalidValue(3))
+        );
+    }
+
+    #[test]
+    fn find_all_matches() {
+        let graph = Graph::from(&Vocabulary::english());
+        let matches = graph
+            .find_iter("xtwone3four")
+            .map(|m| (m.start, m.end, m.value))
+            .collect::<Vec<_>>();
+
+        assert_eq!(matches, [(1, 4, 2), (3, 6, 1), (6, 7, 3), (7, 11, 4)]);
+    }
+
+    #[test]
+    fn failure_links() {
+        let graph = Graph::new([("he", 'a'), ("she", 'b'), ("his", 'c'), ("hers", 'd')]);
+        let matches = graph
+            .find_iter("ushers")
+            .map(|m| (m.start, m.end, m.value))
+            .collect::<Vec<_>>();
+
+        assert_eq!(matches, [(1, 4, 'b'), (2, 4, 'a'), (2, 6, 'd')]);
+    }
+
+    #[test]
+    fn byte_offsets() {
+        let graph = Graph::from(&Vocabulary::english());
+        let matches = graph.find_iter("ñone").collect::<Vec<_>>();
+
+        assert_eq!(
+            matches,
+            [Match {
+                start: 2,
+                end: 5,
+                value: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn reversed() {
+        let graph = Graph::from(&Vocabulary::english()).reversed();
+
+        assert_eq!(graph.parse("eightwo".chars().rev()), Some(&2));
+        assert_eq!(graph.parse("zoneight234".chars().rev()), Some(&4));
+        assert_eq!(graph.parse("abc".chars().rev()), None);
     }
 }
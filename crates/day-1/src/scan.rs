@@ -0,0 +1,134 @@
+//! Byte-level scanning of calibration documents.
+//!
+//! Digits are looked for eight bytes at a time. Bytes of multi-byte UTF-8
+//! sequences all have their high bit set, so they are never mistaken for
+//! ASCII digits and non-ASCII text needs no separate path.
+
+use std::iter::once;
+
+const LOW_BITS: u64 = u64::from_ne_bytes([0x01; 8]);
+const HIGH_BITS: u64 = u64::from_ne_bytes([0x80; 8]);
+
+/// Sets the high bit of every byte of `word` that is an ASCII digit and
+/// clears all other bits.
+fn digit_mask(word: u64) -> u64 {
+    let low = word & !HIGH_BITS;
+    let from_zero = low + (0x80 - b'0' as u64) * LOW_BITS;
+    let past_nine = low + (0x80 - b'9' as u64 - 1) * LOW_BITS;
+
+    from_zero & !past_nine & !word & HIGH_BITS
+}
+
+/// Splits `input` at `\n` like `str::lines`, keeping a trailing `\r` which
+/// is never a digit.
+pub(crate) fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut start = 0;
+
+    memchr::memchr_iter(b'\n', input)
+        .chain(once(input.len()))
+        .filter_map(move |end| {
+            if start == input.len() {
+                return None;
+            }
+
+            let line = &input[start..end];
+            start = end + 1;
+            Some(line)
+        })
+}
+
+pub(crate) fn first_digit(line: &[u8]) -> Option<u32> {
+    let mut chunks = line.chunks_exact(8);
+
+    for chunk in &mut chunks {
+        let mask = digit_mask(u64::from_le_bytes(chunk.try_into().expect("chunk of 8")));
+        if mask != 0 {
+            return Some((chunk[mask.trailing_zeros() as usize / 8] - b'0') as u32);
+        }
+    }
+
+    chunks
+        .remainder()
+        .iter()
+        .find(|b| b.is_ascii_digit())
+        .map(|b| (b - b'0') as u32)
+}
+
+pub(crate) fn last_digit(line: &[u8]) -> Option<u32> {
+    let mut chunks = line.rchunks_exact(8);
+
+    for chunk in &mut chunks {
+        let mask = digit_mask(u64::from_le_bytes(chunk.try_into().expect("chunk of 8")));
+        if mask != 0 {
+            return Some((chunk[7 - mask.leading_zeros() as usize / 8] - b'0') as u32);
+        }
+    }
+
+    chunks
+        .remainder()
+        .iter()
+        .rev()
+        .find(|b| b.is_ascii_digit())
+        .map(|b| (b - b'0') as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_every_byte() {
+        for byte in 0..=u8::MAX {
+            for position in 0..8 {
+                let mut word = [b'.'; 8];
+                word[position] = byte;
+                let expected = if byte.is_ascii_digit() {
+                    0x80 << (position * 8)
+                } else {
+                    0
+                };
+
+                assert_eq!(digit_mask(u64::from_le_bytes(word)), expected, "{byte}");
+            }
+        }
+    }
+
+    #[test]
+    fn split_lines() {
+        fn split(input: &str) -> Vec<&[u8]> {
+            lines(input.as_bytes()).collect()
+        }
+
+        assert_eq!(split(""), Vec::<&[u8]>::new());
+        assert_eq!(split("a"), [b"a"]);
+        assert_eq!(split("a\n"), [b"a"]);
+        assert_eq!(split("a\n\nb"), [&b"a"[..], b"", b"b"]);
+        assert_eq!(split("a\r\nb\r\n"), [b"a\r", b"b\r"]);
+    }
+
+    #[test]
+    fn first_and_last() {
+        for line in [
+            "1",
+            "ab1cdefghijk",
+            "abcdefghijk1",
+            "€1€€€€€€€",
+            "1234567890abcdefg9",
+        ] {
+            let expected_first = line
+                .chars()
+                .find(char::is_ascii_digit)
+                .and_then(|c| c.to_digit(10));
+            let expected_last = line
+                .chars()
+                .rev()
+                .find(char::is_ascii_digit)
+                .and_then(|c| c.to_digit(10));
+
+            assert_eq!(first_digit(line.as_bytes()), expected_first, "{line}");
+            assert_eq!(last_digit(line.as_bytes()), expected_last, "{line}");
+        }
+        assert_eq!(first_digit(b"abcdefghijklmnop"), None);
+        assert_eq!(last_digit(b""), None);
+    }
+}
@@ -0,0 +1,53 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Maze of `size` by `size` tiles (`size` even) filled by one loop snaking
+/// down the columns right of the first and back up the first one.
+fn serpentine(size: usize) -> String {
+    let mut grid = vec![vec!['.'; size]; size];
+    for (y, row) in grid.iter_mut().enumerate() {
+        row[0] = '|';
+        let going_right = y % 2 == 0;
+        for (x, tile) in row.iter_mut().enumerate().skip(1) {
+            *tile = match (y, x) {
+                (0, 1) => '-',
+                (_, 1) if going_right => 'L',
+                (_, 1) => 'F',
+                (_, x) if x == size - 1 && going_right => '7',
+                (_, x) if x == size - 1 => 'J',
+                _ => '-',
+            };
+        }
+    }
+    grid[0][0] = 'S';
+    grid[size - 1][0] = 'L';
+    grid[size - 1][1] = '-';
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn farthest_distance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("farthest_distance");
+    for size in [20, 140] {
+        let input = serpentine(size);
+        assert_eq!(day_10::solve_part_1(&input), (size * size / 2) as u32);
+        assert_eq!(
+            day_10::farthest_distance_bfs(&input),
+            (size * size / 2) as u32
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("pipe merging", size),
+            &input,
+            |b, input| b.iter(|| day_10::solve_part_1(input)),
+        );
+        group.bench_with_input(BenchmarkId::new("bfs", size), &input, |b, input| {
+            b.iter(|| day_10::farthest_distance_bfs(input))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, farthest_distance);
+criterion_main!(benches);
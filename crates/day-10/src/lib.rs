@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Display,
 };
 
@@ -388,19 +388,36 @@ impl Maze {
         }
     }
 
-    fn fill_loop(&mut self) {
-        let pipe = self
-            .pipes
+    fn main_loop(&self) -> &Pipe {
+        self.pipes
             .iter()
             .find(|p| p.is_loop() && p.has_start)
-            .unwrap();
-        let pipe = pipe.parts.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+            .unwrap()
+    }
+
+    /// Coordinates of the tiles forming the loop through the start tile, in
+    /// loop order.
+    pub fn loop_tiles(&self) -> Vec<(i32, i32)> {
+        self.main_loop().parts.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    /// Number of steps along the loop from the start tile to the point
+    /// farthest from it.
+    pub fn farthest_distance(&self) -> u32 {
+        (self.main_loop().parts.len() / 2) as u32
+    }
+
+    /// Coordinates of the tiles enclosed by the loop, row by row.
+    pub fn enclosed_tiles(&self) -> Vec<(i32, i32)> {
+        let pipe = self.loop_tiles().into_iter().collect::<HashSet<_>>();
+        let mut enclosed = Vec::new();
 
         let mut is_crossed = false;
 
         for y in 0..self.height {
             for x in 0..self.width {
-                if pipe.contains(&(x as i32, y as i32)) {
+                let coord = (x as i32, y as i32);
+                if pipe.contains(&coord) {
                     let tile = PartKind::try_from(&self.maze[x + y * self.width]).unwrap();
                     match tile {
                         PartKind::NS | PartKind::SW | PartKind::SE | PartKind::Start => {
@@ -409,12 +426,30 @@ impl Maze {
                         _ => (),
                     }
                 } else if is_crossed {
-                    self.maze[x + y * self.width] = 'I';
-                } else {
-                    self.maze[x + y * self.width] = 'O';
+                    enclosed.push(coord);
                 }
             }
         }
+
+        enclosed
+    }
+
+    /// Marks every tile that is not part of the loop as either enclosed (`I`)
+    /// or outside (`O`), as shown by the `Display` output.
+    pub fn fill_loop(&mut self) {
+        let pipe = self.loop_tiles().into_iter().collect::<HashSet<_>>();
+        let enclosed = self.enclosed_tiles().into_iter().collect::<HashSet<_>>();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coord = (x as i32, y as i32);
+                if pipe.contains(&coord) {
+                    continue;
+                }
+
+                self.maze[x + y * self.width] = if enclosed.contains(&coord) { 'I' } else { 'O' };
+            }
+        }
     }
 }
 
@@ -449,12 +484,7 @@ impl From<&str> for Maze {
 
 impl Display for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let pipe = self
-            .pipes
-            .iter()
-            .find(|p| p.is_loop() && p.has_start)
-            .unwrap();
-        let pipe = pipe.parts.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        let pipe = self.loop_tiles().into_iter().collect::<HashSet<_>>();
 
         for y in 0..self.height {
             for x in 0..self.width {
@@ -477,19 +507,75 @@ impl Display for Maze {
 }
 
 pub fn solve_part_1(input: &str) -> u32 {
-    let maze = Maze::from(input);
-    let pipe = maze
-        .pipes
-        .iter()
-        .find(|p| p.is_loop() && p.has_start)
-        .unwrap();
-    (pipe.parts.len() / 2) as u32
+    Maze::from(input).farthest_distance()
 }
 
 pub fn solve_part_2(input: &str) -> u32 {
-    let mut maze = Maze::from(input);
-    maze.fill_loop();
-    maze.maze.iter().filter(|c| **c == 'I').count() as u32
+    Maze::from(input).enclosed_tiles().len() as u32
+}
+
+/// Directions, as `(dx, dy)`, the tile `tile` opens to.
+fn openings(tile: char) -> &'static [(i32, i32)] {
+    match tile {
+        '|' => &[(0, -1), (0, 1)],
+        '-' => &[(1, 0), (-1, 0)],
+        'L' => &[(0, -1), (1, 0)],
+        'J' => &[(0, -1), (-1, 0)],
+        '7' => &[(0, 1), (-1, 0)],
+        'F' => &[(0, 1), (1, 0)],
+        'S' => &[(0, -1), (0, 1), (1, 0), (-1, 0)],
+        _ => &[],
+    }
+}
+
+/// A named way of solving a part of the puzzle.
+pub type Solver = (&'static str, fn(&str) -> u64);
+
+/// Every way of solving part 1, by name.
+pub fn strategies() -> &'static [Solver] {
+    &[
+        ("pipe merging", |input| solve_part_1(input).into()),
+        ("bfs", |input| farthest_distance_bfs(input).into()),
+    ]
+}
+
+/// Same as [`solve_part_1`], but walks the loop breadth first from the start
+/// tile instead of merging pipes. Kept to cross-check and benchmark the
+/// [`Maze`] against.
+pub fn farthest_distance_bfs(input: &str) -> u32 {
+    let grid = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let tile = |(x, y): (i32, i32)| {
+        grid.get(usize::try_from(y).ok()?)?
+            .get(usize::try_from(x).ok()?)
+            .copied()
+    };
+    let start = grid
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| Some((row.iter().position(|c| *c == 'S')? as i32, y as i32)))
+        .expect("no start tile");
+
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    let mut farthest = 0;
+    while let Some(position) = queue.pop_front() {
+        let distance = distances[&position];
+        farthest = farthest.max(distance);
+
+        for (dx, dy) in openings(tile(position).unwrap_or('.')) {
+            let next = (position.0 + dx, position.1 + dy);
+            let connected = tile(next).is_some_and(|next| openings(next).contains(&(-dx, -dy)));
+            if connected && !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    farthest
 }
 
 #[cfg(test)]
@@ -533,6 +619,38 @@ mod tests {
         assert_eq!(p1.end.y, p2.end.y);
     }
 
+    #[test]
+    fn maze() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let mut maze = Maze::from(input);
+
+        let mut tiles = maze.loop_tiles();
+        tiles.sort_unstable();
+        assert_eq!(
+            tiles,
+            [
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 3)
+            ]
+        );
+        assert_eq!(maze.farthest_distance(), 4);
+        assert_eq!(maze.enclosed_tiles(), [(2, 2)]);
+
+        maze.fill_loop();
+        assert_eq!(maze.maze.iter().filter(|c| **c == 'I').count(), 1);
+        assert_eq!(maze.maze.iter().filter(|c| **c == 'O').count(), 16);
+    }
+
     #[test]
     fn part_1() {
         let input = ".....
@@ -602,4 +720,61 @@ L7JLJL-JLJLJL--JLJ.L";
 
         assert_eq!(solve_part_2(input), 10);
     }
+
+    #[test]
+    fn strategies_agree() {
+        let inputs = [
+            ".....
+.S-7.
+.|.|.
+.L-J.
+.....",
+            "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...",
+            "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+            "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+            ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        ];
+
+        for input in inputs {
+            let expected = u64::from(solve_part_1(input));
+            for (name, solve) in strategies() {
+                assert_eq!(solve(input), expected, "{name}: {input}");
+            }
+        }
+    }
 }
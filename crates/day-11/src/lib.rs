@@ -1,13 +1,17 @@
 use std::collections::{HashSet, VecDeque};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug)]
-struct Galaxy {
-    x: i64,
-    y: i64,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Galaxy {
+    pub x: i64,
+    pub y: i64,
 }
 
 impl Galaxy {
-    fn shortest_path_length(&self, other: &Galaxy) -> u64 {
+    pub fn shortest_path_length(&self, other: &Galaxy) -> u64 {
         self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 }
@@ -21,10 +25,12 @@ impl From<(usize, usize)> for Galaxy {
     }
 }
 
-struct Galaxies {
-    galaxies: Vec<Galaxy>,
-    empty_colums: HashSet<i64>,
-    empty_rows: Vec<i64>,
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Galaxies {
+    pub galaxies: Vec<Galaxy>,
+    pub empty_columns: HashSet<i64>,
+    pub empty_rows: Vec<i64>,
 }
 
 impl From<&str> for Galaxies {
@@ -32,10 +38,10 @@ impl From<&str> for Galaxies {
         let mut galaxies = Vec::new();
 
         let mut empty_rows = Vec::new();
-        let mut empty_colums = HashSet::new();
+        let mut empty_columns = HashSet::new();
         let width = value.lines().next().unwrap().chars().count();
         for i in 0..width {
-            empty_colums.insert(i as i64);
+            empty_columns.insert(i as i64);
         }
 
         for (y, line) in value.lines().enumerate() {
@@ -45,7 +51,7 @@ impl From<&str> for Galaxies {
                 match c {
                     '#' => {
                         line_has_galaxy = false;
-                        empty_colums.remove(&(x as i64));
+                        empty_columns.remove(&(x as i64));
                         galaxies.push((x, y).into());
                     }
                     '.' => (),
@@ -60,25 +66,25 @@ impl From<&str> for Galaxies {
 
         Self {
             galaxies,
-            empty_colums,
+            empty_columns,
             empty_rows,
         }
     }
 }
 
 impl Galaxies {
-    fn expand(&mut self, scale: i64) {
+    pub fn expand(&mut self, scale: i64) {
         for galaxy in &mut self.galaxies {
-            let empty_colums_count =
-                self.empty_colums.iter().filter(|c| **c < galaxy.x).count() as i64;
+            let empty_columns_count =
+                self.empty_columns.iter().filter(|c| **c < galaxy.x).count() as i64;
             let empty_row_count = self.empty_rows.iter().filter(|c| **c < galaxy.y).count() as i64;
 
-            galaxy.x += empty_colums_count * scale - empty_colums_count;
+            galaxy.x += empty_columns_count * scale - empty_columns_count;
             galaxy.y += empty_row_count * scale - empty_row_count;
         }
     }
 
-    fn pair_shortest_path_length_sum(self) -> u64 {
+    pub fn pair_shortest_path_length_sum(self) -> u64 {
         let mut galaxies: VecDeque<Galaxy> = self.galaxies.into();
 
         let mut sum = 0;
@@ -124,6 +130,22 @@ mod tests {
         assert_eq!(solve(input(), 100), 8410);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let json = serde_json::to_string(&Galaxies::from(input())).unwrap();
+        let mut galaxies: Galaxies = serde_json::from_str(&json).unwrap();
+        galaxies.expand(10);
+        assert_eq!(galaxies.pair_shortest_path_length_sum(), 1030);
+
+        let mut galaxies: Galaxies = serde_json::from_str(
+            r#"{"galaxies":[{"x":0,"y":0},{"x":2,"y":0}],"empty_columns":[1],"empty_rows":[]}"#,
+        )
+        .unwrap();
+        galaxies.expand(3);
+        assert_eq!(galaxies.pair_shortest_path_length_sum(), 4);
+    }
+
     fn input() -> &'static str {
         "...#......
 .......#..
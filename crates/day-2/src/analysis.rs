@@ -0,0 +1,214 @@
+//! Statistics treating the sets of a game as samples from an unknown bag.
+//!
+//! Cubes of a set are drawn without replacement and put back into the bag
+//! before the next set, so every set is an independent multivariate
+//! hypergeometric sample.
+
+use std::collections::HashMap;
+
+use crate::{CubeKind, Game, GameSettings, Set};
+
+/// Natural logarithm of the binomial coefficient `n` choose `k`, negative
+/// infinity when `k > n`.
+fn ln_choose(n: u32, k: u32) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+        .sum()
+}
+
+/// Number of cubes drawn of every colour in `set`.
+fn drawn(set: &Set) -> HashMap<CubeKind, u32> {
+    let mut drawn = HashMap::new();
+    for cubes in &set.0 {
+        *drawn.entry(cubes.kind.clone()).or_default() += cubes.count;
+    }
+    drawn
+}
+
+/// Natural logarithm of the probability of drawing the sets of `game` from
+/// `bag`, negative infinity if the game is impossible with it.
+pub fn log_likelihood(game: &Game, bag: &GameSettings) -> f64 {
+    let total = bag.colours().map(|(_, count)| count).sum::<u32>();
+
+    game.sets
+        .iter()
+        .map(|set| {
+            let drawn = drawn(set);
+            let size = drawn.values().sum::<u32>();
+            if size > total || drawn.iter().any(|(kind, count)| *count > bag.count(kind)) {
+                return f64::NEG_INFINITY;
+            }
+
+            drawn
+                .iter()
+                .map(|(kind, count)| ln_choose(bag.count(kind), *count))
+                .sum::<f64>()
+                - ln_choose(total, size)
+        })
+        .sum()
+}
+
+/// Probability of drawing the sets of `game` from `bag`.
+pub fn probability(game: &Game, bag: &GameSettings) -> f64 {
+    log_likelihood(game, bag).exp()
+}
+
+/// Maximum-likelihood bag of `total` cubes for `game`, or `None` if the game
+/// needs more cubes than that.
+///
+/// The total fixes the denominator of the likelihood, which leaves a sum of
+/// terms concave in each colour's count. Handing out the cubes above the
+/// minimal bag one at a time to the colour gaining the most is therefore
+/// optimal.
+pub fn estimate_bag(game: &Game, total: u32) -> Option<GameSettings> {
+    let draws = game.sets.iter().map(drawn).collect::<Vec<_>>();
+
+    let mut bag = GameSettings::minimal_settings(game)
+        .colours()
+        .collect::<Vec<_>>();
+    bag.sort_unstable();
+
+    let minimal = bag.iter().map(|(_, count)| count).sum::<u32>();
+    let extra = total.checked_sub(minimal)?;
+
+    let gain = |kind: &CubeKind, count: u32| {
+        draws
+            .iter()
+            .map(|drawn| drawn.get(kind).copied().unwrap_or(0))
+            .map(|k| ((count + 1) as f64).ln() - ((count + 1 - k) as f64).ln())
+            .sum::<f64>()
+    };
+
+    for _ in 0..extra {
+        let Some((_, count)) = bag
+            .iter_mut()
+            .max_by(|(l, lc), (r, rc)| gain(l, *lc).total_cmp(&gain(r, *rc)).then(r.cmp(l)))
+        else {
+            break;
+        };
+        *count += 1;
+    }
+
+    Some(bag.into_iter().collect())
+}
+
+/// Ids of `games` with their surprise under `bag`, the negative log-likelihood
+/// of their sets, most surprising first. Impossible games come first with an
+/// infinite surprise.
+pub fn rank_by_surprise(games: &[Game], bag: &GameSettings) -> Vec<(u32, f64)> {
+    let mut ranking = games
+        .iter()
+        .map(|game| (game.id, -log_likelihood(game, bag)))
+        .collect::<Vec<_>>();
+    ranking.sort_by(|(_, l), (_, r)| r.total_cmp(l));
+    ranking
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn games() -> Vec<Game> {
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            .lines()
+            .map(Game::from)
+            .collect()
+    }
+
+    fn assert_close(left: f64, right: f64) {
+        assert!((left - right).abs() < 1e-9, "{left} != {right}");
+    }
+
+    #[test]
+    fn probabilities() {
+        let bag = GameSettings::from("1 red, 1 blue");
+        assert_close(probability(&Game::from("Game 1: 1 red"), &bag), 0.5);
+        assert_close(
+            probability(&Game::from("Game 1: 1 red; 1 blue"), &bag),
+            0.25,
+        );
+        assert_close(probability(&Game::from("Game 1: 1 red, 1 blue"), &bag), 1.0);
+        assert_close(probability(&Game::from("Game 1: 2 red"), &bag), 0.0);
+        assert_close(probability(&Game::from("Game 1: 1 green"), &bag), 0.0);
+        // More cubes than the whole bag holds.
+        let game = Game::from("Game 3: 3 red");
+        assert_eq!(log_likelihood(&game, &bag), f64::NEG_INFINITY);
+        assert_close(probability(&game, &bag), 0.0);
+
+        // 3 choose 2 times 2 choose 1, out of 5 choose 3.
+        let bag = GameSettings::from("3 red, 2 blue");
+        assert_close(probability(&Game::from("Game 1: 2 red, 1 blue"), &bag), 0.6);
+        assert_close(
+            probability(&Game::from("Game 1: 1 red, 1 red, 1 blue"), &bag),
+            0.6,
+        );
+    }
+
+    #[test]
+    fn estimate() {
+        let game = Game::from("Game 1: 2 red; 1 red, 1 blue");
+        assert_eq!(estimate_bag(&game, 2), None);
+        assert_eq!(
+            estimate_bag(&game, 3),
+            Some(GameSettings::from("2 red, 1 blue"))
+        );
+        assert_eq!(
+            estimate_bag(&game, 4),
+            Some(GameSettings::from("3 red, 1 blue"))
+        );
+    }
+
+    #[test]
+    fn estimate_is_most_likely() {
+        for game in games() {
+            let kinds = GameSettings::minimal_settings(&game)
+                .colours()
+                .map(|(kind, _)| kind)
+                .collect::<Vec<_>>();
+            let total = 45;
+            let estimate = estimate_bag(&game, total).unwrap();
+
+            let mut best = f64::NEG_INFINITY;
+            for first in 0..=total {
+                for second in 0..=total - first {
+                    let bag = GameSettings::from_iter([
+                        (kinds[0].clone(), first),
+                        (kinds[1].clone(), second),
+                        (kinds[2].clone(), total - first - second),
+                    ]);
+                    best = best.max(log_likelihood(&game, &bag));
+                }
+            }
+
+            assert_close(log_likelihood(&game, &estimate), best);
+        }
+    }
+
+    #[test]
+    fn surprise() {
+        let bag = GameSettings::from("12 red, 13 green, 14 blue");
+        let ranking = rank_by_surprise(&games(), &bag);
+
+        let mut impossible = [ranking[0].0, ranking[1].0];
+        impossible.sort_unstable();
+        assert_eq!(impossible, [3, 4]);
+        assert!(ranking[..2].iter().all(|(_, s)| s.is_infinite()));
+        assert!(ranking[2..].iter().all(|(_, s)| s.is_finite() && *s > 0.0));
+        assert!(ranking.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let games = ["Game 1: 1 red", "Game 2: 1 green", "Game 3: 3 red"].map(Game::from);
+        let ranking = rank_by_surprise(&games, &GameSettings::from("1 red, 1 blue"));
+        assert_eq!(ranking[..2], [(2, f64::INFINITY), (3, f64::INFINITY)]);
+        assert_eq!(ranking[2].0, 1);
+        assert_close(ranking[2].1, 2f64.ln());
+    }
+}
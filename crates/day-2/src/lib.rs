@@ -1,75 +1,305 @@
-struct GameSettings {
-    blue_count: u32,
-    red_count: u32,
-    green_count: u32,
-}
+pub mod analysis;
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Number of cubes of each colour in the bag.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameSettings(HashMap<CubeKind, u32>);
 
 impl GameSettings {
-    fn minimal_settings(game: &Game) -> Self {
-        let mut blue_count = 0;
-        let mut red_count = 0;
-        let mut green_count = 0;
+    pub fn minimal_settings(game: &Game) -> Self {
+        let mut settings = Self::default();
+        settings.fit(game);
+        settings
+    }
 
+    /// Raises the count of every colour to what `game` needs to be valid.
+    pub fn fit(&mut self, game: &Game) {
         for set in &game.sets {
             for cubes in &set.0 {
-                match cubes.kind {
-                    CubeKind::Red => {
-                        if cubes.count > red_count {
-                            red_count = cubes.count
-                        }
-                    }
-                    CubeKind::Blue => {
-                        if cubes.count > blue_count {
-                            blue_count = cubes.count
-                        }
-                    }
-                    CubeKind::Green => {
-                        if cubes.count > green_count {
-                            green_count = cubes.count
-                        }
-                    }
+                let count = self.0.entry(cubes.kind.clone()).or_default();
+                if cubes.count > *count {
+                    *count = cubes.count
                 }
             }
         }
+    }
 
-        Self {
-            blue_count,
-            red_count,
-            green_count,
+    /// Smallest bag for which every one of `games` is valid.
+    pub fn minimal_shared_settings<'a>(games: impl IntoIterator<Item = &'a Game>) -> Self {
+        let mut settings = Self::default();
+        for game in games {
+            settings.fit(game);
         }
+        settings
+    }
+
+    /// Finds a bag of exactly `total` cubes for which as many of `games` as
+    /// possible are valid, and returns it with the number of valid games.
+    ///
+    /// Only the counts some game needs are tried for every colour but the last
+    /// one, which takes all the remaining cubes.
+    pub fn most_valid_games(games: &[Game], total: u32) -> (Self, usize) {
+        let mut colours = games.iter().flat_map(Game::colours).collect::<Vec<_>>();
+        colours.sort_unstable();
+        colours.dedup();
+
+        let needs = games
+            .iter()
+            .map(|game| {
+                let settings = Self::minimal_settings(game);
+                colours
+                    .iter()
+                    .map(|kind| settings.count(kind))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let candidates = (0..colours.len())
+            .map(|i| {
+                let mut counts = needs.iter().map(|need| need[i]).collect::<Vec<_>>();
+                counts.push(0);
+                counts.sort_unstable();
+                counts.dedup();
+                counts
+            })
+            .collect::<Vec<_>>();
+
+        let mut best = (Vec::new(), 0);
+        let mut bag = Vec::with_capacity(colours.len());
+        Self::search(&needs, &candidates, total, &mut bag, &mut best);
+
+        let (bag, count) = best;
+        (colours.into_iter().zip(bag).collect(), count)
+    }
+
+    fn search(
+        needs: &[Vec<u32>],
+        candidates: &[Vec<u32>],
+        left: u32,
+        bag: &mut Vec<u32>,
+        best: &mut (Vec<u32>, usize),
+    ) {
+        if bag.len() + 1 >= candidates.len() {
+            if !candidates.is_empty() {
+                bag.push(left);
+            }
+
+            let count = needs
+                .iter()
+                .filter(|need| need.iter().zip(bag.iter()).all(|(need, have)| need <= have))
+                .count();
+            if best.0.is_empty() || count > best.1 {
+                *best = (bag.clone(), count);
+            }
+
+            if !candidates.is_empty() {
+                bag.pop();
+            }
+            return;
+        }
+
+        for count in &candidates[bag.len()] {
+            if *count > left {
+                break;
+            }
+
+            bag.push(*count);
+            Self::search(needs, candidates, left - count, bag, best);
+            bag.pop();
+        }
+    }
+
+    /// Number of cubes of the colour `kind`, zero if the bag has none.
+    pub fn count(&self, kind: &CubeKind) -> u32 {
+        self.0.get(kind).copied().unwrap_or(0)
     }
 
-    fn power(&self) -> u32 {
-        self.green_count * self.red_count * self.blue_count
+    pub fn colours(&self) -> impl Iterator<Item = (CubeKind, u32)> + '_ {
+        self.0.iter().map(|(kind, count)| (kind.clone(), *count))
+    }
+
+    pub fn power(&self) -> u32 {
+        self.0.values().product()
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum CubeKind {
-    Red,
-    Blue,
-    Green,
+impl FromIterator<(CubeKind, u32)> for GameSettings {
+    fn from_iter<T: IntoIterator<Item = (CubeKind, u32)>>(iter: T) -> Self {
+        let mut settings = Self::default();
+        for (kind, count) in iter {
+            *settings.0.entry(kind).or_default() += count;
+        }
+        settings
+    }
 }
 
-impl From<&str> for CubeKind {
+/// Reads a bag written like a set, e.g. `12 red, 13 green, 14 blue`. Every
+/// entry is a count and a colour, the counts of a colour listed twice adding
+/// up. A blank string is an empty bag.
+impl FromStr for GameSettings {
+    type Err = SettingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut colours = ColourTable::new();
+        let mut settings = Self::default();
+        if s.trim().is_empty() {
+            return Ok(settings);
+        }
+
+        for entry in s.split(',') {
+            let entry = entry.trim();
+            let (count, name) = entry
+                .split_once(' ')
+                .and_then(|(count, name)| Some((count.parse().ok()?, name.trim())))
+                .ok_or_else(|| SettingsError::InvalidCount(entry.to_string()))?;
+            // A number among the words of the name is most likely the count
+            // of the next colour, with the comma before it missing.
+            let kind = colours
+                .kind(name)
+                .filter(|_| {
+                    !name
+                        .split_whitespace()
+                        .any(|word| word.parse::<u32>().is_ok())
+                })
+                .ok_or_else(|| SettingsError::InvalidColour(entry.to_string()))?;
+
+            let total = settings.0.entry(kind).or_default();
+            *total = total
+                .checked_add(count)
+                .ok_or_else(|| SettingsError::InvalidCount(entry.to_string()))?;
+        }
+
+        Ok(settings)
+    }
+}
+
+/// Panics if `value` isn't a valid bag, see the [`FromStr`] implementation.
+impl From<&str> for GameSettings {
     fn from(value: &str) -> Self {
-        match value {
-            "blue" => CubeKind::Blue,
-            "red" => CubeKind::Red,
-            "green" => CubeKind::Green,
-            _ => unreachable!("only above matches are part of the input"),
+        value.parse().expect("valid bag")
+    }
+}
+
+/// Error reading a bag, with the offending entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsError {
+    InvalidCount(String),
+    InvalidColour(String),
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsError::InvalidCount(entry) => {
+                write!(f, "{entry:?}: expected a count followed by a colour")
+            }
+            SettingsError::InvalidColour(entry) => write!(f, "{entry:?}: invalid colour name"),
+        }
+    }
+}
+
+impl Error for SettingsError {}
+
+/// Colour names read so far, so that every kind of the same colour parsed
+/// with the table shares one name.
+#[derive(Debug, Default)]
+pub struct ColourTable(HashSet<Arc<str>>);
+
+impl ColourTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Colour named `name`, or `None` if the name is invalid, see
+    /// [`CubeKind::new`].
+    pub fn kind(&mut self, name: &str) -> Option<CubeKind> {
+        if !CubeKind::is_valid(name) {
+            return None;
         }
+
+        let name = match self.0.get(name) {
+            Some(name) => name.clone(),
+            None => {
+                let name = Arc::<str>::from(name);
+                self.0.insert(name.clone());
+                name
+            }
+        };
+        Some(CubeKind(name))
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Cubes {
-    count: u32,
-    kind: CubeKind,
+/// Colour of a cube. The name is shared between clones, so kinds are cheap to
+/// clone. Kinds compare and order by name.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CubeKind(Arc<str>);
+
+impl CubeKind {
+    /// Colour named `name`, or `None` if the name couldn't be read back from
+    /// a game: it is empty, starts or ends with whitespace, or contains a `,`
+    /// or `;`.
+    pub fn new(name: &str) -> Option<Self> {
+        Self::is_valid(name).then(|| Self(name.into()))
+    }
+
+    fn is_valid(name: &str) -> bool {
+        !name.is_empty() && name.trim() == name && !name.contains([',', ';'])
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
 }
 
-impl From<&str> for Cubes {
+/// Panics if `value` isn't a valid colour name, see [`CubeKind::new`].
+impl From<&str> for CubeKind {
     fn from(value: &str) -> Self {
+        Self::new(value).expect("valid colour name")
+    }
+}
+
+impl Debug for CubeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CubeKind").field(&self.name()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CubeKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CubeKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::new(&name).ok_or_else(|| D::Error::custom(format!("invalid colour name {name:?}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cubes {
+    pub count: u32,
+    pub kind: CubeKind,
+}
+
+impl Cubes {
+    /// Reads cubes like `3 blue`, taking the colour from `colours`.
+    pub fn parse(value: &str, colours: &mut ColourTable) -> Self {
         let (count, kind) = value
             .trim()
             .split_once(' ')
@@ -77,69 +307,98 @@ impl From<&str> for Cubes {
 
         Self {
             count: count.parse().expect("always valid int passed as input"),
-            kind: kind.into(),
+            kind: colours.kind(kind).expect("valid colour name"),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Set(Vec<Cubes>);
-
-impl From<&str> for Set {
+impl From<&str> for Cubes {
     fn from(value: &str) -> Self {
+        Self::parse(value, &mut ColourTable::new())
+    }
+}
+
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.count, self.kind.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Set(pub Vec<Cubes>);
+
+impl Set {
+    /// Reads a set, which is empty if `value` is blank, taking the colours
+    /// from `colours`.
+    pub fn parse(value: &str, colours: &mut ColourTable) -> Self {
         let mut set = Vec::new();
+        if value.trim().is_empty() {
+            return Self(set);
+        }
 
         for cubes in value.split(',') {
-            set.push(cubes.into());
+            set.push(Cubes::parse(cubes, colours));
         }
 
         Self(set)
     }
-}
 
-#[derive(Debug, PartialEq)]
-struct Game {
-    id: u32,
-    sets: Vec<Set>,
-}
+    /// Merges the draws of the same colour and orders them by colour name.
+    pub fn normalize(&mut self) {
+        self.0.sort_by(|l, r| l.kind.cmp(&r.kind));
 
-impl Game {
-    fn is_valid(&self, settings: &GameSettings) -> bool {
-        for set in &self.sets {
-            for cubes in &set.0 {
-                match cubes.kind {
-                    CubeKind::Red => {
-                        if cubes.count > settings.red_count {
-                            return false;
-                        }
-                    }
-                    CubeKind::Blue => {
-                        if cubes.count > settings.blue_count {
-                            return false;
-                        }
-                    }
-                    CubeKind::Green => {
-                        if cubes.count > settings.green_count {
-                            return false;
-                        }
-                    }
-                }
+        let mut merged: Vec<Cubes> = Vec::with_capacity(self.0.len());
+        for cubes in self.0.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.kind == cubes.kind => last.count += cubes.count,
+                _ => merged.push(cubes),
             }
         }
+        self.0 = merged;
+    }
+}
 
-        true
+impl Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, cubes) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", cubes)?;
+        }
+        Ok(())
     }
 }
 
-impl From<&str> for Game {
+impl From<&str> for Set {
     fn from(value: &str) -> Self {
+        Self::parse(value, &mut ColourTable::new())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Set>,
+}
+
+impl Game {
+    /// Reads a game line, taking the colours from `colours` so that games
+    /// read with the same table share colour names.
+    pub fn parse(value: &str, colours: &mut ColourTable) -> Self {
         let (game_id, game_sets) = value.split_once(':').expect(": always part of the input");
         let game_id = game_id.trim_start_matches("Game ");
 
         let mut sets = Vec::new();
 
-        for set in game_sets.split(';') {
-            sets.push(set.into());
+        // Nothing after the colon is a game without sets, a blank set is an
+        // empty one.
+        if !game_sets.is_empty() {
+            for set in game_sets.split(';') {
+                sets.push(Set::parse(set, colours));
+            }
         }
 
         Self {
@@ -147,20 +406,69 @@ impl From<&str> for Game {
             sets,
         }
     }
+
+    pub fn is_valid(&self, settings: &GameSettings) -> bool {
+        self.violations(settings).next().is_none()
+    }
+
+    /// Draws that need more cubes than `settings` has, with the index of the
+    /// set they were drawn in.
+    pub fn violations<'a>(
+        &'a self,
+        settings: &'a GameSettings,
+    ) -> impl Iterator<Item = (usize, &'a Cubes)> + 'a {
+        self.sets.iter().enumerate().flat_map(move |(index, set)| {
+            set.0
+                .iter()
+                .filter(|cubes| cubes.count > settings.count(&cubes.kind))
+                .map(move |cubes| (index, cubes))
+        })
+    }
+
+    /// Normalizes every set, see [`Set::normalize`].
+    pub fn normalize(&mut self) {
+        for set in &mut self.sets {
+            set.normalize();
+        }
+    }
+
+    /// Colours drawn in any of the sets.
+    pub fn colours(&self) -> impl Iterator<Item = CubeKind> + '_ {
+        self.sets
+            .iter()
+            .flat_map(|set| set.0.iter().map(|cubes| cubes.kind.clone()))
+    }
 }
 
-pub fn solve_part_1(input: &str) -> u32 {
-    let mut result = 0;
+impl From<&str> for Game {
+    fn from(value: &str) -> Self {
+        Self::parse(value, &mut ColourTable::new())
+    }
+}
 
-    let settings = GameSettings {
-        blue_count: 14,
-        red_count: 12,
-        green_count: 13,
-    };
+/// Writes the game in the puzzle format, e.g. `Game 1: 3 blue, 4 red; 2 green`.
+/// A game without sets is written as `Game 1:`, one with a single empty set
+/// as `Game 1: `.
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, " {}", set)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn solve_part_1(input: &str, settings: &GameSettings) -> u32 {
+    let mut colours = ColourTable::new();
+    let mut result = 0;
 
     for line in input.lines() {
-        let game = Game::from(line);
-        if game.is_valid(&settings) {
+        let game = Game::parse(line, &mut colours);
+        if game.is_valid(settings) {
             result += game.id;
         }
     }
@@ -168,12 +476,24 @@ pub fn solve_part_1(input: &str) -> u32 {
     result
 }
 
+/// Sums the power of the minimal bag of every game. A colour drawn in any game
+/// but missing from another counts as zero cubes in the latter's bag.
 pub fn solve_part_2(input: &str) -> u32 {
+    let mut colours = ColourTable::new();
+    let games = input
+        .lines()
+        .map(|line| Game::parse(line, &mut colours))
+        .collect::<Vec<_>>();
+    let colours = games.iter().flat_map(Game::colours).collect::<HashSet<_>>();
+
     let mut result = 0;
 
-    for line in input.lines() {
-        let game = Game::from(line);
-        let settings = GameSettings::minimal_settings(&game);
+    for game in &games {
+        let mut settings = colours
+            .iter()
+            .map(|kind| (kind.clone(), 0))
+            .collect::<GameSettings>();
+        settings.fit(game);
         result += settings.power();
     }
 
@@ -183,6 +503,79 @@ pub fn solve_part_2(input: &str) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{collection::vec, prelude::*};
+
+    fn cubes() -> impl Strategy<Value = Cubes> {
+        let kind =
+            "[a-z ,;:]{0,6}".prop_filter_map("invalid colour name", |name| CubeKind::new(&name));
+        (0..100u32, kind).prop_map(|(count, kind)| Cubes { count, kind })
+    }
+
+    fn game() -> impl Strategy<Value = Game> {
+        (any::<u32>(), vec(vec(cubes(), 0..6).prop_map(Set), 0..6))
+            .prop_map(|(id, sets)| Game { id, sets })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip(game in game()) {
+            prop_assert_eq!(Game::from(game.to_string().as_str()), game);
+        }
+
+        #[test]
+        fn normalize_keeps_totals(game in game()) {
+            let mut normalized = game.clone();
+            normalized.normalize();
+
+            for (set, normalized) in game.sets.iter().zip(&normalized.sets) {
+                let mut names = set.0.iter().map(|c| c.kind.name()).collect::<Vec<_>>();
+                names.sort_unstable();
+                names.dedup();
+                let normalized_names = normalized.0.iter().map(|c| c.kind.name());
+                prop_assert!(normalized_names.eq(names));
+
+                for cubes in &normalized.0 {
+                    let total = set
+                        .0
+                        .iter()
+                        .filter(|c| c.kind == cubes.kind)
+                        .map(|c| c.count)
+                        .sum::<u32>();
+                    prop_assert_eq!(cubes.count, total);
+                }
+            }
+            prop_assert_eq!(Game::from(normalized.to_string().as_str()), normalized);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        assert_eq!(Game::from(line).to_string(), line);
+
+        let mut game = Game::from("Game 7: 3 red, 1 blue, 2 red; 4 green");
+        game.normalize();
+        assert_eq!(game.to_string(), "Game 7: 1 blue, 5 red; 4 green");
+
+        for (game, line) in [
+            (vec![], "Game 1:"),
+            (vec![Set(vec![])], "Game 1: "),
+            (vec![Set(vec![]), Set(vec![])], "Game 1: ; "),
+        ] {
+            let game = Game { id: 1, sets: game };
+            assert_eq!(game.to_string(), line);
+            assert_eq!(Game::from(line), game);
+        }
+    }
+
+    #[test]
+    fn colour_names() {
+        assert_eq!(CubeKind::new("dark red").unwrap().name(), "dark red");
+        assert_eq!(CubeKind::new("red:ish").unwrap().name(), "red:ish");
+        for name in ["", " red", "red ", "red, blue", "red; blue"] {
+            assert_eq!(CubeKind::new(name), None, "{name:?}");
+        }
+    }
 
     #[test]
     fn parse_game() {
@@ -190,7 +583,7 @@ mod tests {
             id: 1,
             sets: vec![Set(vec![Cubes {
                 count: 3,
-                kind: CubeKind::Red,
+                kind: CubeKind::from("red"),
             }])],
         };
         let game = Game::from("Game 1: 3 red");
@@ -206,7 +599,140 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        assert_eq!(solve_part_1(input), 8);
+        assert_eq!(
+            solve_part_1(input, &GameSettings::from("12 red, 13 green, 14 blue")),
+            8
+        );
+    }
+
+    #[test]
+    fn minimal_shared_settings() {
+        let games = input().lines().map(Game::from).collect::<Vec<_>>();
+        let settings = GameSettings::minimal_shared_settings(&games);
+
+        assert_eq!(settings, GameSettings::from("20 red, 13 green, 15 blue"));
+        assert!(games.iter().all(|game| game.is_valid(&settings)));
+    }
+
+    #[test]
+    fn most_valid_games() {
+        let games = input().lines().map(Game::from).collect::<Vec<_>>();
+
+        for (total, expected) in [(48, 5), (39, 4), (10, 1), (0, 0)] {
+            let (settings, count) = GameSettings::most_valid_games(&games, total);
+
+            assert_eq!(count, expected, "{total} cubes");
+            assert_eq!(
+                settings.colours().map(|(_, count)| count).sum::<u32>(),
+                total
+            );
+            assert_eq!(
+                games.iter().filter(|game| game.is_valid(&settings)).count(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn violations() {
+        let settings = GameSettings::from("12 red, 13 green, 14 blue");
+        let games = input().lines().map(Game::from).collect::<Vec<_>>();
+        let violations = |game: &Game| {
+            game.violations(&settings)
+                .map(|(set, cubes)| (set, cubes.count, cubes.kind.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(violations(&games[0]), []);
+        assert_eq!(violations(&games[2]), [(0, 20, "red".into())]);
+        assert_eq!(
+            violations(&games[3]),
+            [(2, 15, "blue".into()), (2, 14, "red".into())]
+        );
+    }
+
+    #[test]
+    fn more_colours() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 yellow
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 1 blue, 1 purple, 1 red, 1 green, 1 yellow";
+
+        let settings = GameSettings::from("12 red, 13 green, 14 blue, 2 yellow");
+        assert_eq!(settings.count(&CubeKind::from("yellow")), 2);
+        assert_eq!(settings.count(&CubeKind::from("purple")), 0);
+        assert_eq!(solve_part_1(input, &settings), 3);
+
+        // Only game 3 draws every colour, the others need zero purple cubes.
+        assert_eq!(solve_part_2(input), 1);
+        assert_eq!(solve_part_2("Game 1: 2 red, 3 cyan\nGame 2: 4 cyan"), 6);
+    }
+
+    #[test]
+    fn settings_from_str() {
+        assert_eq!(
+            "12 red, 13 green, 14 blue".parse(),
+            Ok(GameSettings::from_iter([
+                (CubeKind::from("red"), 12),
+                (CubeKind::from("green"), 13),
+                (CubeKind::from("blue"), 14),
+            ]))
+        );
+        assert_eq!(
+            " 2 light blue ".parse(),
+            Ok(GameSettings::from_iter([("light blue".into(), 2)]))
+        );
+        assert_eq!("".parse(), Ok(GameSettings::default()));
+
+        for (input, error) in [
+            ("red: 12", SettingsError::InvalidCount("red: 12".into())),
+            ("12 red, red", SettingsError::InvalidCount("red".into())),
+            ("12 red,", SettingsError::InvalidCount("".into())),
+            ("-1 red", SettingsError::InvalidCount("-1 red".into())),
+            (
+                "4294967295 red, 1 red",
+                SettingsError::InvalidCount("1 red".into()),
+            ),
+            (
+                "12 red 13 green",
+                SettingsError::InvalidColour("12 red 13 green".into()),
+            ),
+            (
+                "12 red; 13 green",
+                SettingsError::InvalidColour("12 red; 13 green".into()),
+            ),
+        ] {
+            assert_eq!(input.parse::<GameSettings>(), Err(error), "{input}");
+        }
+        assert_eq!(
+            SettingsError::InvalidCount("red: 12".into()).to_string(),
+            "\"red: 12\": expected a count followed by a colour"
+        );
+    }
+
+    #[test]
+    fn colour_table() {
+        let mut colours = ColourTable::new();
+        let first = Game::parse("Game 1: 3 red, 1 blue", &mut colours);
+        let second = Game::parse("Game 2: 2 red", &mut colours);
+
+        let name = |game: &Game| game.sets[0].0[0].kind.0.clone();
+        assert!(Arc::ptr_eq(&name(&first), &name(&second)));
+        assert!(!Arc::ptr_eq(
+            &name(&first),
+            &name(&Game::from("Game 3: 2 red"))
+        ));
+        assert_eq!(colours.kind("red, blue"), None);
+    }
+
+    #[test]
+    fn colours_compare_by_name() {
+        assert_eq!(CubeKind::from("magenta"), CubeKind::from("magenta"));
+        assert_ne!(CubeKind::from("magenta"), CubeKind::from("cyan"));
+        assert_eq!(CubeKind::from("magenta").name(), "magenta");
+        assert_eq!(
+            GameSettings::from("1 red, 2 red"),
+            GameSettings::from("3 red")
+        );
     }
 
     #[test]
@@ -219,4 +745,34 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
         assert_eq!(solve_part_2(input), 2286);
     }
+
+    fn input() -> &'static str {
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let game = Game::from("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green");
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(serde_json::from_str::<Game>(&json).unwrap(), game);
+
+        let settings: GameSettings =
+            serde_json::from_str(r#"{"blue":14,"red":12,"green":13}"#).unwrap();
+        let game: Game = serde_json::from_str(
+            r#"{"id":1,"sets":[[{"count":3,"kind":"blue"},{"count":4,"kind":"red"}]]}"#,
+        )
+        .unwrap();
+        assert!(game.is_valid(&settings));
+        assert_eq!(GameSettings::minimal_settings(&game).power(), 12);
+        assert_eq!(
+            serde_json::to_string(&GameSettings::from("4 red")).unwrap(),
+            r#"{"red":4}"#
+        );
+        assert!(serde_json::from_str::<CubeKind>(r#""red, blue""#).is_err());
+    }
 }
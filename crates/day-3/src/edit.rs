@@ -0,0 +1,273 @@
+//! Editing single cells of a crawled schematic.
+//!
+//! An edit only touches the numbers in the cell's row that run into it, the
+//! symbol in it and the numbers and symbols around those, keeping the sums of
+//! the cache up to date.
+
+use std::collections::BTreeMap;
+
+use crate::{Cache, Coord, Number, NumberKind, SymbolKind};
+
+impl Cache {
+    /// Writes `char` into the cell at `coord` and updates the part numbers and
+    /// gears around it.
+    pub fn set(&mut self, coord: Coord, char: char) {
+        let mut changed_cells = vec![coord];
+
+        if let Some(index) = self.symbol_map.get(&coord).copied() {
+            self.remove_symbol(index);
+        }
+
+        // Digits of the numbers this cell is in or next to, which may be
+        // split or joined by the edit.
+        let row = [
+            coord.x.checked_sub(1),
+            Some(coord.x),
+            coord.x.checked_add(1),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|x| self.number_map.get(&Coord { x, y: coord.y }).copied())
+        .collect::<Vec<_>>();
+        let mut digits = BTreeMap::new();
+        let mut indexes = Vec::new();
+        for index in row {
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+        indexes.sort_unstable_by(|l, r| r.cmp(l));
+        for index in indexes {
+            let span = self.number_spans[index].clone();
+            digits.extend(
+                span.columns
+                    .clone()
+                    .zip(self.numbers[index].0.digits.chars()),
+            );
+            changed_cells.extend(span.cells());
+            self.remove_number(index);
+        }
+
+        digits.remove(&coord.x);
+        if char.is_ascii_digit() {
+            digits.insert(coord.x, char);
+        } else if self.rules.is_symbol(char) {
+            self.push_symbol(coord, char);
+        }
+
+        let mut new_numbers = Vec::new();
+        let mut run: Vec<(usize, char)> = Vec::new();
+        for (x, digit) in digits.into_iter().chain(Some((usize::MAX, '.'))) {
+            if run.last().is_some_and(|(last, _)| last + 1 != x) {
+                let coords = run
+                    .iter()
+                    .map(|(x, _)| Coord { x: *x, y: coord.y })
+                    .collect::<Vec<_>>();
+                let digits = run.drain(..).map(|(_, digit)| digit).collect();
+
+                changed_cells.extend(coords.iter().copied());
+                new_numbers.push(self.numbers.len());
+                self.push_number(coords, Number::new(digits));
+            }
+            run.push((x, digit));
+        }
+
+        for index in self.adjacent_numbers(&coord).into_iter().chain(new_numbers) {
+            self.update_number(index);
+        }
+
+        let mut symbols = Vec::new();
+        for cell in changed_cells {
+            let around = cell.neighbours(self.rules.neighbourhood).chain(Some(cell));
+            for coord in around.collect::<Vec<_>>() {
+                if let Some(index) = self.symbol_map.get(&coord) {
+                    if !symbols.contains(index) {
+                        symbols.push(*index);
+                    }
+                }
+            }
+        }
+        for index in symbols {
+            self.update_symbol(index);
+        }
+    }
+
+    /// Removes the number at `index`, moving the last number into its place.
+    fn remove_number(&mut self, index: usize) {
+        let (number, kind) = self.numbers.swap_remove(index);
+        if kind == NumberKind::PartNumber {
+            self.part_number_sum.remove(number.value.map(u64::from));
+        }
+        for cell in self.number_spans.swap_remove(index).cells() {
+            self.number_map.remove(&cell);
+        }
+
+        if let Some(span) = self.number_spans.get(index) {
+            for cell in span.cells() {
+                self.number_map.insert(cell, index);
+            }
+        }
+    }
+
+    /// Removes the symbol at `index`, moving the last symbol into its place.
+    fn remove_symbol(&mut self, index: usize) {
+        let (_, kind) = self.symbols.swap_remove(index);
+        if let SymbolKind::Gear(value) = kind {
+            self.gear_value_sum.remove(value);
+        }
+        self.symbol_map
+            .remove(&self.symbol_coords.swap_remove(index));
+
+        if let Some(coord) = self.symbol_coords.get(index) {
+            self.symbol_map.insert(*coord, index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use crate::{Adjacency, Crawler, GearValue, Neighbourhood, OverflowError, Rules, Symbols};
+
+    use super::*;
+
+    fn crawl(grid: &[Vec<char>], rules: &Rules) -> Cache {
+        let input = grid
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut cache = Crawler::crawl(input.lines(), rules);
+        cache.find_part_numbers_and_gears();
+        cache
+    }
+
+    #[test]
+    fn edit_example() {
+        let mut grid = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let rules = Rules::default();
+        let mut cache = crawl(&grid, &rules);
+
+        for (x, y, char, part_numbers, gears) in [
+            // 467 becomes 4670.
+            (3, 0, '0', 4361 - 467 + 4670, 4670 * 35 + 755 * 598),
+            // Joins it with 114, still a gear with 35.
+            (4, 0, '9', 4361 - 467 + 46709114, 46709114 * 35 + 755 * 598),
+            // Splits it into 467 and 9114, leaving three numbers next to the
+            // gear.
+            (3, 0, '.', 4361 + 9114, 755 * 598),
+            // Replaces a digit with a symbol.
+            (0, 0, '*', 4361 + 9114 - 467 + 67, 755 * 598),
+            // Cuts 9114 back to 114, away from the gear.
+            (4, 0, '.', 4361 - 467 + 67, 67 * 35 + 755 * 598),
+        ] {
+            grid[y][x] = char;
+            cache.set(Coord { x, y }, char);
+
+            assert_eq!(cache.part_number_sum(), Ok(part_numbers), "{x}, {y}");
+            assert_eq!(cache.gear_value_sum(), Ok(gears), "{x}, {y}");
+        }
+    }
+
+    #[test]
+    fn overflow_and_back() {
+        let mut grid = "99999.99999\n.....*.....\n99999.99999"
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let rules = Rules {
+            adjacency: Adjacency::AtLeast(2),
+            ..Default::default()
+        };
+        let mut cache = crawl(&grid, &rules);
+        assert_eq!(cache.gear_value_sum(), Err(OverflowError));
+
+        let mut edit = |x: usize, y: usize, char: char| {
+            grid[y][x] = char;
+            cache.set(Coord { x, y }, char);
+            assert_eq!(
+                cache.part_number_sum(),
+                crawl(&grid, &rules).part_number_sum()
+            );
+            cache.gear_value_sum()
+        };
+
+        // Moves the top left number away from the gear.
+        assert_eq!(edit(4, 0, '.'), Ok(99999 * 99999 * 99999));
+        assert_eq!(edit(4, 0, '9'), Err(OverflowError));
+        // Joins the top row into a number past u32::MAX.
+        assert_eq!(edit(5, 0, '9'), Err(OverflowError));
+        // Cuts it back to nine digits.
+        assert_eq!(edit(0, 0, '.'), Err(OverflowError));
+        assert_eq!(edit(1, 0, '.'), Ok(999999999 * 99999 * 99999));
+    }
+
+    /// Column, row and new character of a cell.
+    type Edit = (usize, usize, char);
+
+    /// Grid of up to 12 by 8 cells and up to 100 edits within it. Most cells
+    /// are digits, so that numbers past `u32::MAX` and gear values past
+    /// `u64::MAX` come and go.
+    fn edits() -> impl Strategy<Value = (Vec<Vec<char>>, Vec<Edit>)> {
+        let cells = ['.', '.', '1', '2', '5', '0', '9', '9', '9', '*', '#'];
+        (1..=12usize, 1..=8usize).prop_flat_map(move |(width, height)| {
+            let grid = vec(vec(select(cells.to_vec()), width), height);
+            let edit = (0..width, 0..height, select(cells.to_vec()));
+            (grid, vec(edit, 0..100))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn random_edits((start, edits) in edits()) {
+            let rules = [
+                Rules::default(),
+                Rules {
+                    adjacency: Adjacency::AtLeast(1),
+                    gear_value: GearValue::Sum,
+                    neighbourhood: Neighbourhood::Four,
+                    ..Default::default()
+                },
+                Rules {
+                    symbols: Symbols::Only(HashSet::from(['*'])),
+                    ..Default::default()
+                },
+                Rules {
+                    adjacency: Adjacency::AtLeast(2),
+                    ..Default::default()
+                },
+            ];
+
+            for rules in &rules {
+                let mut grid = start.clone();
+                let mut cache = crawl(&grid, rules);
+
+                for (x, y, char) in edits.iter().copied() {
+                    grid[y][x] = char;
+                    cache.set(Coord { x, y }, char);
+
+                    let expected = crawl(&grid, rules);
+                    prop_assert_eq!(cache.part_number_sum(), expected.part_number_sum());
+                    prop_assert_eq!(cache.gear_value_sum(), expected.gear_value_sum());
+                    prop_assert_eq!(cache.numbers(), expected.numbers());
+                    prop_assert_eq!(cache.symbols(), expected.symbols());
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,56 @@
-use std::collections::HashMap;
+mod edit;
+mod window;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Coord {
-    x: usize,
-    y: usize,
+pub use window::{stream_sums, RowParts, Window};
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Write},
+    ops::Range,
+};
+
+/// A part number, gear value or sum too large for its integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value too large")
+    }
+}
+
+impl Error for OverflowError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    /// Coordinates around this one in `neighbourhood`, leaving out those that
+    /// would fall before the first row or column.
+    fn neighbours(&self, neighbourhood: Neighbourhood) -> impl Iterator<Item = Coord> + '_ {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .filter(move |(dx, dy)| neighbourhood == Neighbourhood::Eight || dx * dy == 0)
+        .filter_map(|(dx, dy)| {
+            Some(Coord {
+                x: self.x.checked_add_signed(dx)?,
+                y: self.y.checked_add_signed(dy)?,
+            })
+        })
+    }
 }
 
 impl From<(usize, usize)> for Coord {
@@ -15,10 +62,101 @@ impl From<(usize, usize)> for Coord {
     }
 }
 
+/// Characters of a schematic that are symbols.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbols {
+    /// Every character that is neither a digit nor blank.
+    Any,
+    /// Only these characters, all others are treated as blank.
+    Only(HashSet<char>),
+}
+
+/// Number of adjacent part numbers a gear needs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjacency {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Adjacency {
+    fn allows(self, count: usize) -> bool {
+        match self {
+            Adjacency::Exactly(n) => count == n,
+            Adjacency::AtLeast(n) => count >= n,
+        }
+    }
+}
+
+/// Cells a symbol is adjacent to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighbourhood {
+    /// Sharing an edge.
+    Four,
+    /// Sharing an edge or a corner.
+    Eight,
+}
+
+/// How the part numbers around a gear combine into its value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GearValue {
+    /// Product of the part numbers. Gear values are `u64`; a product too
+    /// large for it is an [`OverflowError`].
+    Product,
+    Sum,
+}
+
+impl GearValue {
+    /// Value of a gear with the part numbers `values`, `None` if one of them
+    /// or the result doesn't fit in a `u64`.
+    fn combine(&self, mut values: impl Iterator<Item = Option<u64>>) -> Option<u64> {
+        match self {
+            GearValue::Product => {
+                values.try_fold(1u64, |product, value| product.checked_mul(value?))
+            }
+            GearValue::Sum => values.try_fold(0u64, |sum, value| sum.checked_add(value?)),
+        }
+    }
+}
+
+/// Rules for reading a schematic. The default ones are the puzzle's.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    pub blank: char,
+    pub symbols: Symbols,
+    /// Symbol that is a gear when enough part numbers are adjacent to it.
+    pub gear: char,
+    pub adjacency: Adjacency,
+    pub neighbourhood: Neighbourhood,
+    pub gear_value: GearValue,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            blank: '.',
+            symbols: Symbols::Any,
+            gear: '*',
+            adjacency: Adjacency::Exactly(2),
+            neighbourhood: Neighbourhood::Eight,
+            gear_value: GearValue::Product,
+        }
+    }
+}
+
+impl Rules {
+    fn is_symbol(&self, char: char) -> bool {
+        match &self.symbols {
+            Symbols::Any => char != self.blank,
+            Symbols::Only(symbols) => symbols.contains(&char),
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum SymbolKind {
     Symbol,
-    Gear(u32),
+    /// Gear with its value, `None` if the value doesn't fit in a `u64`.
+    Gear(Option<u64>),
 }
 
 #[derive(Debug)]
@@ -31,7 +169,18 @@ enum NumberKind {
 }
 
 #[derive(Debug)]
-struct Number(u32);
+struct Number {
+    digits: String,
+    /// `None` if the number doesn't fit in a `u32`.
+    value: Option<u32>,
+}
+
+impl Number {
+    fn new(digits: String) -> Self {
+        let value = digits.parse().ok();
+        Self { digits, value }
+    }
+}
 
 #[derive(Debug)]
 struct NumberBuffer {
@@ -61,30 +210,102 @@ impl NumberBuffer {
     fn yeld(&mut self) -> (Vec<Coord>, Number) {
         (
             self.coords.split_off(0),
-            Number(
-                self.buffer
-                    .split_off(0)
-                    .parse()
-                    .expect("contains only ascii digits"),
-            ),
+            Number::new(self.buffer.split_off(0)),
         )
     }
 }
 
-struct Cache {
+/// Cells of a schematic a number is written in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+impl Span {
+    fn cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.columns.clone().map(|x| Coord { x, y: self.row })
+    }
+}
+
+/// Number of a schematic with the symbols adjacent to it, given as indices
+/// into [`Cache::symbols`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberInfo {
+    /// `None` if the number doesn't fit in a `u32`.
+    pub value: Option<u32>,
+    pub span: Span,
+    pub symbols: Vec<usize>,
+}
+
+/// Symbol of a schematic with the numbers adjacent to it, given as indices
+/// into [`Cache::numbers`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub symbol: char,
+    pub coord: Coord,
+    /// Value of the symbol if it is a gear.
+    pub gear: Option<Result<u64, OverflowError>>,
+    pub numbers: Vec<usize>,
+}
+
+/// Running sum of values, some of which may not fit their integer type,
+/// that values can be taken out of again.
+#[derive(Default)]
+struct Total {
+    sum: u128,
+    /// Number of values added that don't fit.
+    overflowed: usize,
+}
+
+impl Total {
+    fn add(&mut self, value: Option<u64>) {
+        match value {
+            Some(value) => self.sum += value as u128,
+            None => self.overflowed += 1,
+        }
+    }
+
+    fn remove(&mut self, value: Option<u64>) {
+        match value {
+            Some(value) => self.sum -= value as u128,
+            None => self.overflowed -= 1,
+        }
+    }
+
+    fn get<T: TryFrom<u128>>(&self) -> Result<T, OverflowError> {
+        if self.overflowed > 0 {
+            return Err(OverflowError);
+        }
+        T::try_from(self.sum).map_err(|_| OverflowError)
+    }
+}
+
+/// Numbers and symbols of a schematic, by position.
+pub struct Cache {
+    rules: Rules,
     symbol_map: HashMap<Coord, usize>,
     number_map: HashMap<Coord, usize>,
     symbols: Vec<(Symbol, SymbolKind)>,
     numbers: Vec<(Number, NumberKind)>,
+    symbol_coords: Vec<Coord>,
+    number_spans: Vec<Span>,
+    part_number_sum: Total,
+    gear_value_sum: Total,
 }
 
 impl Cache {
-    fn new() -> Self {
+    fn new(rules: Rules) -> Self {
         Self {
+            rules,
             symbol_map: HashMap::new(),
             number_map: HashMap::new(),
             symbols: Vec::new(),
             numbers: Vec::new(),
+            symbol_coords: Vec::new(),
+            number_spans: Vec::new(),
+            part_number_sum: Total::default(),
+            gear_value_sum: Total::default(),
         }
     }
 
@@ -92,71 +313,222 @@ impl Cache {
         let index = self.symbols.len();
         self.symbol_map.insert(coord, index);
         self.symbols.push((Symbol(symbol), SymbolKind::Symbol));
+        self.symbol_coords.push(coord);
     }
 
     fn push_number(&mut self, coords: Vec<Coord>, number: Number) {
         let index = self.numbers.len();
+        self.number_spans.push(Span {
+            row: coords[0].y,
+            columns: coords[0].x..coords[coords.len() - 1].x + 1,
+        });
         for coord in coords {
             self.number_map.insert(coord, index);
         }
         self.numbers.push((number, NumberKind::Number));
     }
 
-    fn find_part_numbers_and_gears(&mut self) {
-        for (coord, index) in &self.symbol_map {
-            let coords: Vec<Coord> = vec![
-                (coord.x - 1, coord.y).into(),
-                (coord.x, coord.y - 1).into(),
-                (coord.x - 1, coord.y - 1).into(),
-                (coord.x + 1, coord.y).into(),
-                (coord.x, coord.y + 1).into(),
-                (coord.x + 1, coord.y + 1).into(),
-                (coord.x - 1, coord.y + 1).into(),
-                (coord.x + 1, coord.y - 1).into(),
-            ];
-
-            let mut counted_indexes = Vec::new();
-
-            let mut part_number_count = 0;
-            let mut gear_ratio = 1;
-
-            for coord in &coords {
-                if let Some(index) = self.number_map.get(coord) {
-                    if counted_indexes.contains(&index) {
-                        continue;
-                    }
+    /// Indices of the distinct numbers adjacent to `coord`.
+    fn adjacent_numbers(&self, coord: &Coord) -> Vec<usize> {
+        let mut indexes = Vec::new();
+        for coord in coord.neighbours(self.rules.neighbourhood) {
+            if let Some(index) = self.number_map.get(&coord) {
+                if !indexes.contains(index) {
+                    indexes.push(*index);
+                }
+            }
+        }
+        indexes
+    }
 
-                    counted_indexes.push(index);
+    /// Marks the number at `index` as a part number if a symbol is adjacent
+    /// to it, keeping the part number sum up to date.
+    fn update_number(&mut self, index: usize) {
+        let is_part = self.number_spans[index].cells().any(|cell| {
+            cell.neighbours(self.rules.neighbourhood)
+                .any(|coord| self.symbol_map.contains_key(&coord))
+        });
+
+        let (number, kind) = &mut self.numbers[index];
+        let value = number.value.map(u64::from);
+        if *kind == NumberKind::PartNumber {
+            self.part_number_sum.remove(value);
+        }
+        *kind = if is_part {
+            self.part_number_sum.add(value);
+            NumberKind::PartNumber
+        } else {
+            NumberKind::Number
+        };
+    }
 
-                    let (number, kind) = self
-                        .numbers
-                        .get_mut(*index)
-                        .expect("always exists if an index is found");
-                    *kind = NumberKind::PartNumber;
+    /// Marks the symbol at `index` as a gear if the rules make it one, keeping
+    /// the gear value sum up to date.
+    fn update_symbol(&mut self, index: usize) {
+        let numbers = self.adjacent_numbers(&self.symbol_coords[index]);
 
-                    part_number_count += 1;
-                    gear_ratio *= number.0;
-                }
-            }
+        let (symbol, kind) = &mut self.symbols[index];
+        if let SymbolKind::Gear(value) = kind {
+            self.gear_value_sum.remove(*value);
+        }
+        *kind = SymbolKind::Symbol;
+
+        if symbol.0 == self.rules.gear && self.rules.adjacency.allows(numbers.len()) {
+            let values = numbers
+                .iter()
+                .map(|index| self.numbers[*index].0.value.map(u64::from));
+            let value = self.rules.gear_value.combine(values);
+            self.gear_value_sum.add(value);
+            *kind = SymbolKind::Gear(value);
+        }
+    }
+
+    /// Marks the numbers adjacent to a symbol as part numbers and the gears
+    /// among the symbols.
+    pub fn find_part_numbers_and_gears(&mut self) {
+        for index in 0..self.numbers.len() {
+            self.update_number(index);
+        }
+        for index in 0..self.symbols.len() {
+            self.update_symbol(index);
+        }
+    }
 
-            if part_number_count == 2 {
-                let (symbol, kind) = self
-                    .symbols
-                    .get_mut(*index)
-                    .expect("always exists if an index is found");
-                if symbol.0 == '*' {
-                    *kind = SymbolKind::Gear(gear_ratio);
+    /// Sum of the part numbers, an error if a part number or the sum
+    /// doesn't fit in a `u32`.
+    pub fn part_number_sum(&self) -> Result<u32, OverflowError> {
+        self.part_number_sum.get()
+    }
+
+    /// Sum of the values of the gears, an error if a gear value or the sum
+    /// doesn't fit in a `u64`.
+    pub fn gear_value_sum(&self) -> Result<u64, OverflowError> {
+        self.gear_value_sum.get()
+    }
+
+    /// Indices of the numbers and of the symbols in the order they appear,
+    /// and adjacent symbol and number positions in those orders.
+    fn edges(&self) -> (Vec<usize>, Vec<usize>, Vec<(usize, usize)>) {
+        let mut numbers = (0..self.numbers.len()).collect::<Vec<_>>();
+        numbers.sort_unstable_by_key(|index| {
+            let span = &self.number_spans[*index];
+            (span.row, span.columns.start)
+        });
+        let mut symbols = (0..self.symbols.len()).collect::<Vec<_>>();
+        symbols.sort_unstable_by_key(|index| {
+            let coord = &self.symbol_coords[*index];
+            (coord.y, coord.x)
+        });
+
+        let mut number_positions = vec![0; numbers.len()];
+        for (position, index) in numbers.iter().enumerate() {
+            number_positions[*index] = position;
+        }
+
+        let mut edges = Vec::new();
+        for (position, index) in symbols.iter().enumerate() {
+            let mut adjacent = self
+                .adjacent_numbers(&self.symbol_coords[*index])
+                .into_iter()
+                .map(|number| (position, number_positions[number]))
+                .collect::<Vec<_>>();
+            adjacent.sort_unstable();
+            edges.extend(adjacent);
+        }
+
+        (numbers, symbols, edges)
+    }
+
+    /// Numbers in the order they appear, with their adjacent symbols.
+    pub fn numbers(&self) -> Vec<NumberInfo> {
+        let (numbers, _, edges) = self.edges();
+        let mut numbers = numbers
+            .into_iter()
+            .map(|index| NumberInfo {
+                value: self.numbers[index].0.value,
+                span: self.number_spans[index].clone(),
+                symbols: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        for (symbol, number) in edges {
+            numbers[number].symbols.push(symbol);
+        }
+        numbers
+    }
+
+    /// Symbols in the order they appear, with their adjacent numbers. Gears
+    /// are only known once [`Cache::find_part_numbers_and_gears`] ran.
+    pub fn symbols(&self) -> Vec<SymbolInfo> {
+        let (_, symbols, edges) = self.edges();
+        let mut symbols = symbols
+            .into_iter()
+            .map(|index| {
+                let (symbol, kind) = &self.symbols[index];
+                SymbolInfo {
+                    symbol: symbol.0,
+                    coord: self.symbol_coords[index],
+                    gear: match kind {
+                        SymbolKind::Gear(value) => Some(value.ok_or(OverflowError)),
+                        SymbolKind::Symbol => None,
+                    },
+                    numbers: Vec::new(),
                 }
-            }
+            })
+            .collect::<Vec<_>>();
+        for (symbol, number) in edges {
+            symbols[symbol].numbers.push(number);
         }
+        symbols
+    }
+
+    /// Numbers not adjacent to any symbol.
+    pub fn non_part_numbers(&self) -> Vec<NumberInfo> {
+        self.numbers()
+            .into_iter()
+            .filter(|number| number.symbols.is_empty())
+            .collect()
+    }
+
+    /// Graph of symbols and the numbers adjacent to them in DOT format. Gears
+    /// are drawn as double boxes, numbers that are not part numbers dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph schematic {\n");
+
+        for (index, symbol) in self.symbols().iter().enumerate() {
+            let label = format!("{:?}", symbol.symbol.to_string());
+            let shape = if symbol.gear.is_some() {
+                "box, peripheries=2"
+            } else {
+                "box"
+            };
+            writeln!(dot, "    s{index} [label={label}, shape={shape}];").expect("infallible");
+        }
+        for (index, (number, position)) in self.numbers().iter().zip(self.edges().0).enumerate() {
+            let style = if number.symbols.is_empty() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            let label = match number.value {
+                Some(value) => value.to_string(),
+                None => self.numbers[position].0.digits.clone(),
+            };
+            writeln!(dot, "    n{index} [label=\"{label}\"{style}];").expect("infallible");
+        }
+        for (symbol, number) in self.edges().2 {
+            writeln!(dot, "    s{symbol} -- n{number};").expect("infallible");
+        }
+
+        dot.push_str("}\n");
+        dot
     }
 }
 
-struct Crawler;
+pub struct Crawler;
 
 impl Crawler {
-    fn crawl<'a>(input: impl Iterator<Item = &'a str>) -> Cache {
-        let mut cache = Cache::new();
+    pub fn crawl<'a>(input: impl Iterator<Item = &'a str>, rules: &Rules) -> Cache {
+        let mut cache = Cache::new(rules.clone());
 
         for (y, line) in input.enumerate() {
             let mut buffer = NumberBuffer::new();
@@ -166,19 +538,19 @@ impl Crawler {
                     char if char.is_ascii_digit() => {
                         buffer.push(char, (x, y).into());
                     }
-                    '.' => {
+                    char if rules.is_symbol(char) => {
                         if !buffer.is_empty() {
                             let (coords, number) = buffer.yeld();
                             cache.push_number(coords, number);
                         }
+
+                        cache.push_symbol((x, y).into(), char);
                     }
-                    char => {
+                    _ => {
                         if !buffer.is_empty() {
                             let (coords, number) = buffer.yeld();
                             cache.push_number(coords, number);
                         }
-
-                        cache.push_symbol((x, y).into(), char);
                     }
                 }
             }
@@ -193,32 +565,20 @@ impl Crawler {
 }
 
 pub fn solve_part_1<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
-    let mut cache = Crawler::crawl(input);
+    let mut cache = Crawler::crawl(input, &Rules::default());
     cache.find_part_numbers_and_gears();
     cache
-        .numbers
-        .iter()
-        .filter(|(_, kind)| kind == &NumberKind::PartNumber)
-        .map(|(number, _)| number.0)
-        .sum()
+        .part_number_sum()
+        .expect("part number sum fits in a u32")
 }
 
-pub fn solve_part_2<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
-    let mut cache = Crawler::crawl(input);
+pub fn solve_part_2<'a>(input: impl Iterator<Item = &'a str>) -> u64 {
+    let mut cache = Crawler::crawl(input, &Rules::default());
     cache.find_part_numbers_and_gears();
     cache
-        .symbols
-        .iter()
-        .filter_map(|(_, kind)| {
-            if let SymbolKind::Gear(ratio) = kind {
-                Some(ratio)
-            } else {
-                None
-            }
-        })
-        .sum()
+        .gear_value_sum()
+        .expect("gear value sum fits in a u64")
 }
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +614,217 @@ mod tests {
 
         assert_eq!(solve_part_2(input.lines()), 467835);
     }
+
+    #[test]
+    fn symbols_on_edges() {
+        for input in [
+            // Corners.
+            "*1.\n2..\n..9",
+            ".1*\n..2\n9..",
+            "9..\n2..\n*1.",
+            "..9\n..2\n.1*",
+            // Edges.
+            ".*.\n1.2\n...\n.9.",
+            ".9.\n...\n1.2\n.*.",
+            "1..\n*.9\n2..",
+            "..1\n9.*\n..2",
+        ] {
+            assert_eq!(solve_part_1(input.lines()), 3, "{input}");
+            assert_eq!(solve_part_2(input.lines()), 2, "{input}");
+        }
+    }
+
+    #[test]
+    fn rules() {
+        let input = "1.2.3
+.*.*.
+4.#.5";
+        let solve = |rules: Rules| {
+            let mut cache = Crawler::crawl(input.lines(), &rules);
+            cache.find_part_numbers_and_gears();
+            (
+                cache.part_number_sum().unwrap(),
+                cache.gear_value_sum().unwrap(),
+            )
+        };
+
+        assert_eq!(solve(Rules::default()), (15, 0));
+        assert_eq!(
+            solve(Rules {
+                adjacency: Adjacency::AtLeast(2),
+                ..Default::default()
+            }),
+            (15, 8 + 30)
+        );
+        assert_eq!(
+            solve(Rules {
+                adjacency: Adjacency::Exactly(3),
+                gear_value: GearValue::Sum,
+                ..Default::default()
+            }),
+            (15, 7 + 10)
+        );
+        assert_eq!(
+            solve(Rules {
+                neighbourhood: Neighbourhood::Four,
+                ..Default::default()
+            }),
+            (0, 0)
+        );
+        assert_eq!(
+            solve(Rules {
+                symbols: Symbols::Only(HashSet::from(['#'])),
+                adjacency: Adjacency::AtLeast(1),
+                gear: '#',
+                ..Default::default()
+            }),
+            (0, 0)
+        );
+        assert_eq!(
+            solve(Rules {
+                blank: '#',
+                neighbourhood: Neighbourhood::Four,
+                ..Default::default()
+            }),
+            (15, 0)
+        );
+    }
+
+    #[test]
+    fn large_gear_products() {
+        let rules = Rules {
+            adjacency: Adjacency::AtLeast(2),
+            ..Default::default()
+        };
+
+        for (input, product) in [
+            ("999.999\n...*...\n999.999", Ok(999u64.pow(4))),
+            ("999.999\n999*999\n999.999", Ok(999u64.pow(6))),
+            ("9999.9999\n....*....\n9999.9999", Ok(9999u64.pow(4))),
+            ("99999.99999\n.....*.....\n99999.99999", Err(OverflowError)),
+            ("99999.99999\n99999*99999\n99999.99999", Err(OverflowError)),
+            // Past u32::MAX before any multiplication.
+            ("4294967296*1", Err(OverflowError)),
+        ] {
+            let mut cache = Crawler::crawl(input.lines(), &rules);
+            cache.find_part_numbers_and_gears();
+
+            assert_eq!(cache.gear_value_sum(), product, "{input}");
+            assert_eq!(
+                stream_sums(input.lines(), &rules).map(|sums| sums.1),
+                product,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn large_part_numbers() {
+        for (input, sum) in [
+            ("4294967295*", Ok(u32::MAX)),
+            ("4294967295*\n..........1", Err(OverflowError)),
+            ("4294967296*", Err(OverflowError)),
+            // Not a part number, so never added.
+            ("4294967296.*", Ok(0)),
+        ] {
+            let mut cache = Crawler::crawl(input.lines(), &Rules::default());
+            cache.find_part_numbers_and_gears();
+
+            assert_eq!(cache.part_number_sum(), sum, "{input}");
+            assert_eq!(
+                stream_sums(input.lines(), &Rules::default()).map(|sums| sums.0),
+                sum,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn queries() {
+        let mut cache = Crawler::crawl("12*.\n..34\n5..#".lines(), &Rules::default());
+        cache.find_part_numbers_and_gears();
+
+        assert_eq!(
+            cache.numbers(),
+            [
+                NumberInfo {
+                    value: Some(12),
+                    span: Span {
+                        row: 0,
+                        columns: 0..2
+                    },
+                    symbols: vec![0],
+                },
+                NumberInfo {
+                    value: Some(34),
+                    span: Span {
+                        row: 1,
+                        columns: 2..4
+                    },
+                    symbols: vec![0, 1],
+                },
+                NumberInfo {
+                    value: Some(5),
+                    span: Span {
+                        row: 2,
+                        columns: 0..1
+                    },
+                    symbols: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            cache.symbols(),
+            [
+                SymbolInfo {
+                    symbol: '*',
+                    coord: Coord { x: 2, y: 0 },
+                    gear: Some(Ok(408)),
+                    numbers: vec![0, 1],
+                },
+                SymbolInfo {
+                    symbol: '#',
+                    coord: Coord { x: 3, y: 2 },
+                    gear: None,
+                    numbers: vec![1],
+                },
+            ]
+        );
+        assert_eq!(
+            cache
+                .non_part_numbers()
+                .iter()
+                .map(|number| number.value)
+                .collect::<Vec<_>>(),
+            [Some(5)]
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let mut cache = Crawler::crawl("12*.\n..34\n5..\"".lines(), &Rules::default());
+        cache.find_part_numbers_and_gears();
+
+        assert_eq!(
+            cache.to_dot(),
+            r#"graph schematic {
+    s0 [label="*", shape=box, peripheries=2];
+    s1 [label="\"", shape=box];
+    n0 [label="12"];
+    n1 [label="34"];
+    n2 [label="5", style=dashed];
+    s0 -- n0;
+    s0 -- n1;
+    s1 -- n1;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn single_cell() {
+        assert_eq!(solve_part_1("*".lines()), 0);
+        assert_eq!(solve_part_1("1*".lines()), 1);
+        assert_eq!(solve_part_2("1*2".lines()), 2);
+    }
 }
@@ -0,0 +1,293 @@
+//! Streaming crawl of a schematic keeping only three rows at a time.
+//!
+//! A row is complete once the row below it is known: every number and symbol
+//! it can be adjacent to is then in the window.
+
+use std::ops::Range;
+
+use crate::{Neighbourhood, OverflowError, Rules};
+
+/// Numbers and symbols of a single row.
+#[derive(Default)]
+struct Row {
+    /// Numbers with their columns, `None` if too large for a `u32`.
+    numbers: Vec<(Range<usize>, Option<u32>)>,
+    symbols: Vec<(usize, char)>,
+}
+
+impl Row {
+    fn parse(line: &str, rules: &Rules) -> Self {
+        let mut row = Row::default();
+        let mut start = None;
+        let mut value = Some(0u32);
+        let mut end = 0;
+
+        for (x, char) in line.chars().enumerate() {
+            end = x + 1;
+            if let Some(digit) = char.to_digit(10) {
+                start.get_or_insert(x);
+                value = value.and_then(|value| value.checked_mul(10)?.checked_add(digit));
+                continue;
+            }
+
+            if let Some(start) = start.take() {
+                row.numbers.push((start..x, value));
+                value = Some(0);
+            }
+            if rules.is_symbol(char) {
+                row.symbols.push((x, char));
+            }
+        }
+        if let Some(start) = start {
+            row.numbers.push((start..end, value));
+        }
+
+        row
+    }
+}
+
+/// Whether a symbol in column `x` touches a number in `columns`, `same_row`
+/// telling if both are in the same row or in neighbouring ones.
+fn touches(x: usize, columns: &Range<usize>, same_row: bool, neighbourhood: Neighbourhood) -> bool {
+    match (neighbourhood, same_row) {
+        (_, true) => x + 1 == columns.start || x == columns.end,
+        (Neighbourhood::Four, false) => columns.contains(&x),
+        (Neighbourhood::Eight, false) => columns.start <= x + 1 && x <= columns.end,
+    }
+}
+
+/// Part numbers and gear values of a completed row.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RowParts {
+    pub part_numbers: Vec<u32>,
+    pub gears: Vec<u64>,
+}
+
+/// Crawls a schematic one line at a time, the same way as
+/// [`Crawler::crawl`](crate::Crawler::crawl) and
+/// [`Cache::find_part_numbers_and_gears`](crate::Cache::find_part_numbers_and_gears)
+/// would.
+pub struct Window<'a> {
+    rules: &'a Rules,
+    previous: Row,
+    current: Option<Row>,
+}
+
+impl<'a> Window<'a> {
+    pub fn new(rules: &'a Rules) -> Self {
+        Self {
+            rules,
+            previous: Row::default(),
+            current: None,
+        }
+    }
+
+    /// Adds the next line, returning the parts of the line before it, which
+    /// is now complete. Fails if one of its part numbers doesn't fit in a
+    /// `u32` or one of its gear values in a `u64`.
+    pub fn push(&mut self, line: &str) -> Result<Option<RowParts>, OverflowError> {
+        let next = Row::parse(line, self.rules);
+        let parts = self.complete(&next);
+
+        if let Some(current) = self.current.replace(next) {
+            self.previous = current;
+        }
+        parts
+    }
+
+    /// Returns the parts of the last line.
+    pub fn finish(self) -> Result<Option<RowParts>, OverflowError> {
+        self.complete(&Row::default())
+    }
+
+    fn complete(&self, next: &Row) -> Result<Option<RowParts>, OverflowError> {
+        let Some(current) = self.current.as_ref() else {
+            return Ok(None);
+        };
+        let neighbourhood = self.rules.neighbourhood;
+        let rows = [(&self.previous, false), (current, true), (next, false)];
+
+        let part_numbers = current
+            .numbers
+            .iter()
+            .filter(|(columns, _)| {
+                rows.iter().any(|(row, same_row)| {
+                    row.symbols
+                        .iter()
+                        .any(|(x, _)| touches(*x, columns, *same_row, neighbourhood))
+                })
+            })
+            .map(|(_, value)| value.ok_or(OverflowError))
+            .collect::<Result<_, _>>()?;
+
+        let gears = current
+            .symbols
+            .iter()
+            .filter(|(_, symbol)| *symbol == self.rules.gear)
+            .filter_map(|(x, _)| {
+                let values = rows
+                    .iter()
+                    .flat_map(|(row, same_row)| {
+                        row.numbers
+                            .iter()
+                            .filter(|(columns, _)| touches(*x, columns, *same_row, neighbourhood))
+                            .map(|(_, value)| value.map(u64::from))
+                    })
+                    .collect::<Vec<_>>();
+
+                self.rules.adjacency.allows(values.len()).then(|| {
+                    self.rules
+                        .gear_value
+                        .combine(values.into_iter())
+                        .ok_or(OverflowError)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Some(RowParts {
+            part_numbers,
+            gears,
+        }))
+    }
+}
+
+/// Sums of the part numbers and of the gear values of `input`, read one line
+/// at a time. Fails like [`Cache::part_number_sum`](crate::Cache::part_number_sum)
+/// and [`Cache::gear_value_sum`](crate::Cache::gear_value_sum) do, on the
+/// first row that overflows.
+pub fn stream_sums<'a>(
+    input: impl Iterator<Item = &'a str>,
+    rules: &Rules,
+) -> Result<(u32, u64), OverflowError> {
+    let mut window = Window::new(rules);
+    let mut sums = (0u32, 0u64);
+    let mut add = |parts: RowParts| -> Result<(), OverflowError> {
+        for value in parts.part_numbers {
+            sums.0 = sums.0.checked_add(value).ok_or(OverflowError)?;
+        }
+        for value in parts.gears {
+            sums.1 = sums.1.checked_add(value).ok_or(OverflowError)?;
+        }
+        Ok(())
+    };
+
+    for line in input {
+        if let Some(parts) = window.push(line)? {
+            add(parts)?;
+        }
+    }
+    if let Some(parts) = window.finish()? {
+        add(parts)?;
+    }
+
+    Ok(sums)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use super::*;
+    use crate::{Adjacency, Crawler, GearValue, Symbols};
+
+    /// Schematic of up to 12 by 12 cells with digits, blanks and symbols,
+    /// its rows one cell shorter at random. Most cells are digits, so that
+    /// some numbers and gear values overflow.
+    fn schematic() -> impl Strategy<Value = String> {
+        let cells = [
+            '.', '.', '1', '2', '3', '7', '9', '9', '9', '0', '*', '*', '#', '+',
+        ];
+        (1..=12usize, 1..=12usize)
+            .prop_flat_map(move |(width, height)| {
+                let row = (any::<bool>(), vec(select(cells.to_vec()), width));
+                vec(row, height)
+            })
+            .prop_map(|rows| {
+                rows.into_iter()
+                    .map(|(shorter, mut row)| {
+                        if shorter && row.len() > 1 {
+                            row.pop();
+                        }
+                        row.into_iter().collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn same_as_cache(input in schematic()) {
+            let rules = [
+                Rules::default(),
+                Rules {
+                    adjacency: Adjacency::AtLeast(1),
+                    ..Default::default()
+                },
+                Rules {
+                    neighbourhood: Neighbourhood::Four,
+                    adjacency: Adjacency::Exactly(1),
+                    ..Default::default()
+                },
+                Rules {
+                    adjacency: Adjacency::AtLeast(2),
+                    ..Default::default()
+                },
+                Rules {
+                    symbols: Symbols::Only(HashSet::from(['*', '+'])),
+                    gear: '+',
+                    adjacency: Adjacency::AtLeast(2),
+                    gear_value: GearValue::Sum,
+                    ..Default::default()
+                },
+            ];
+
+            for rules in &rules {
+                let mut cache = Crawler::crawl(input.lines(), rules);
+                cache.find_part_numbers_and_gears();
+
+                let expected = cache
+                    .part_number_sum()
+                    .and_then(|part_numbers| Ok((part_numbers, cache.gear_value_sum()?)));
+
+                prop_assert_eq!(
+                    stream_sums(input.lines(), rules),
+                    expected,
+                    "{:?}",
+                    rules
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rows_complete_one_line_late() {
+        let rules = Rules::default();
+        let mut window = Window::new(&rules);
+
+        assert_eq!(window.push("467..114.."), Ok(None));
+        assert_eq!(
+            window.push("...*......"),
+            Ok(Some(RowParts {
+                part_numbers: vec![467],
+                gears: vec![],
+            }))
+        );
+        assert_eq!(
+            window.push("..35..633."),
+            Ok(Some(RowParts {
+                part_numbers: vec![],
+                gears: vec![467 * 35],
+            }))
+        );
+        assert_eq!(
+            window.finish(),
+            Ok(Some(RowParts {
+                part_numbers: vec![35],
+                gears: vec![],
+            }))
+        );
+    }
+}
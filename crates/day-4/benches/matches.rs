@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::SmallRng, RngExt, SeedableRng};
+
+/// Match counting as it was before the bitset, with two hash sets per card.
+fn matches_hashed(line: &str) -> u32 {
+    let (winning_numbers, card_numbers) = line
+        .split_once(':')
+        .expect("failed to split at ':'")
+        .1
+        .split_once('|')
+        .expect("failed to split numbers at '|'");
+
+    let winning_numbers = winning_numbers
+        .split_whitespace()
+        .filter_map(|n| n.parse::<u32>().ok())
+        .collect::<HashSet<_>>();
+
+    let card_numbers = card_numbers
+        .split_whitespace()
+        .filter_map(|n| n.parse::<u32>().ok())
+        .collect::<HashSet<_>>();
+
+    winning_numbers.intersection(&card_numbers).count() as u32
+}
+
+/// Builds a table of `count` cards with ten winning and 25 held numbers
+/// below `bound`.
+fn generate(count: usize, bound: u32) -> String {
+    let mut rng = SmallRng::seed_from_u64(1);
+
+    let mut input = String::new();
+    for id in 1..=count {
+        input.push_str(&format!("Card {id:4}:"));
+        for _ in 0..10 {
+            input.push_str(&format!(" {:2}", rng.random_range(0..bound)));
+        }
+        input.push_str(" |");
+        for _ in 0..25 {
+            input.push_str(&format!(" {:2}", rng.random_range(0..bound)));
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+fn matches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matches");
+    for (name, bound) in [("below 100", 100), ("below 1000", 1000)] {
+        let input = generate(100_000, bound);
+        assert!(input
+            .lines()
+            .all(|line| day_4::matches(line) == matches_hashed(line)));
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("hash sets", name), &input, |b, input| {
+            b.iter(|| input.lines().map(matches_hashed).sum::<u32>())
+        });
+        group.bench_with_input(BenchmarkId::new("bitset", name), &input, |b, input| {
+            b.iter(|| input.lines().map(day_4::matches).sum::<u32>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, matches);
+criterion_main!(benches);
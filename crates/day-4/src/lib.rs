@@ -1,59 +1,219 @@
-use std::collections::{HashSet, VecDeque};
+mod numbers;
+mod scoring;
 
-struct Card {
-    wins: u32,
+pub use numbers::{matches, NumberSet};
+pub use scoring::{Capped, Linear, Puzzle, Scoring, Weighted, WrapAround};
+
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::AddAssign,
+};
+
+#[cfg(feature = "num-bigint")]
+pub use num_bigint::BigUint;
+
+/// Integer type copies of cards are counted in, like `u32`, `u128` or, with
+/// the `num-bigint` feature, `BigUint`.
+pub trait Count: Clone + for<'a> AddAssign<&'a Self> + From<u32> {}
+
+impl<T: Clone + for<'a> AddAssign<&'a T> + From<u32>> Count for T {}
+
+/// A scratchcard with its numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub held: Vec<u32>,
+    /// Number of held numbers that also appear among the winning numbers.
+    pub matches: u32,
 }
 
-impl From<&str> for Card {
+/// Earlier name of [`Scratchcard`].
+pub type Card = Scratchcard;
+
+impl Scratchcard {
+    /// Number of held numbers that also appear among the winning numbers,
+    /// the same as [`Scratchcard::matches`].
+    pub fn wins(&self) -> u32 {
+        self.matches
+    }
+
+    /// Points the card is worth under the puzzle's rules.
+    pub fn points(&self) -> u32 {
+        Puzzle.points(self)
+    }
+
+    /// Distinct held numbers that are winning numbers.
+    pub fn matching(&self) -> impl Iterator<Item = u32> + '_ {
+        let winning = self.winning.iter().collect::<HashSet<_>>();
+        self.held
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter(move |number| winning.contains(number))
+            .copied()
+    }
+}
+
+impl From<&str> for Scratchcard {
     fn from(value: &str) -> Self {
-        let (winning_numbers, card_numbers) = value
-            .split_once(':')
-            .expect("failed to split at ':'")
-            .1
+        let (card, numbers) = value.split_once(':').expect("failed to split at ':'");
+        let (winning_numbers, held_numbers) = numbers
             .split_once('|')
             .expect("failed to split numbers at '|'");
 
-        let winning_numbers = winning_numbers
+        let id = card
+            .trim_start_matches("Card")
+            .trim()
+            .parse()
+            .expect("card id is a number");
+
+        let winning = winning_numbers
             .split_whitespace()
             .filter_map(|n| n.parse::<u32>().ok())
-            .collect::<HashSet<_>>();
+            .collect::<Vec<_>>();
 
-        let card_numbers = card_numbers
+        let held = held_numbers
             .split_whitespace()
             .filter_map(|n| n.parse::<u32>().ok())
-            .collect::<HashSet<_>>();
+            .collect::<Vec<_>>();
 
-        let winning_count = winning_numbers.intersection(&card_numbers).count() as u32;
+        let matches = winning
+            .iter()
+            .copied()
+            .collect::<NumberSet>()
+            .intersection_len(&held.iter().copied().collect());
 
         Self {
-            wins: winning_count,
+            id,
+            winning,
+            held,
+            matches,
         }
     }
 }
 
+/// One point for the first match, doubled for every further one.
+fn doubling(matches: u32) -> u32 {
+    if matches > 0 {
+        2u32.pow(matches - 1)
+    } else {
+        0
+    }
+}
+
+/// Copies of a card won by the matches of another card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Source<T = u32> {
+    pub id: u32,
+    pub copies: T,
+}
+
+/// Instances of a card once all copies are won, and where its copies came
+/// from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry<T = u32> {
+    pub id: u32,
+    pub instances: T,
+    pub sources: Vec<Source<T>>,
+}
+
+/// Part 2 played out card by card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ledger<T = u32> {
+    pub entries: Vec<LedgerEntry<T>>,
+}
+
+impl<T: Count> Ledger<T> {
+    /// Plays out `cards` in a single pass, where each instance of a card wins
+    /// copies of the cards `scoring` gives. Copies of a card won after it was
+    /// played, like those wrapping around, win nothing.
+    pub fn new(cards: &[Scratchcard], scoring: &impl Scoring) -> Self {
+        let mut entries = cards
+            .iter()
+            .map(|card| LedgerEntry {
+                id: card.id,
+                instances: T::from(1),
+                sources: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        for (index, card) in cards.iter().enumerate() {
+            let copies = entries[index].instances.clone();
+
+            for won in scoring.won_cards(card, index, cards.len()) {
+                let entry = &mut entries[won];
+                entry.instances += &copies;
+                entry.sources.push(Source {
+                    id: card.id,
+                    copies: copies.clone(),
+                });
+            }
+        }
+
+        Self { entries }
+    }
+
+    pub fn entry(&self, id: u32) -> Option<&LedgerEntry<T>> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Total number of card instances.
+    pub fn total(&self) -> T {
+        let mut total = T::from(0);
+        for entry in &self.entries {
+            total += &entry.instances;
+        }
+        total
+    }
+}
+
+/// Sum of the points of the cards of `input` under `scoring`.
+pub fn solve_part_1_with<'a>(input: impl Iterator<Item = &'a str>, scoring: &impl Scoring) -> u32 {
+    input
+        .map(|line| scoring.points(&Scratchcard::from(line)))
+        .sum()
+}
+
+/// Sum of the points of the cards of `input` under the [`Puzzle`] rules,
+/// counting matches straight from the bytes of each line.
 pub fn solve_part_1<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
     let mut result = 0;
     for line in input {
-        let card = Card::from(line);
-        if card.wins > 0 {
-            result += 2u32.pow(card.wins - 1);
-        }
+        result += doubling(matches(line));
     }
     result
 }
 
+/// Number of card instances at the end of part 2 under `scoring`, counted in
+/// `T`. The whole table is played out through a [`Ledger`], since policies
+/// like [`WrapAround`] send copies to cards already read.
+pub fn count_instances_with<'a, T: Count>(
+    input: impl Iterator<Item = &'a str>,
+    scoring: &impl Scoring,
+) -> T {
+    let cards = input.map(Scratchcard::from).collect::<Vec<_>>();
+    Ledger::new(&cards, scoring).total()
+}
+
+/// Number of card instances at the end of part 2 under the [`Puzzle`] rules,
+/// counted in `T`. Copies only go to the following cards, so only the copies
+/// still owed to upcoming cards are kept while reading the table.
 #[allow(clippy::mut_range_bound)]
-pub fn solve_part_2<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
-    let mut result = 0;
-    let mut copies = VecDeque::<u32>::new();
+pub fn count_instances<'a, T: Count>(input: impl Iterator<Item = &'a str>) -> T {
+    let mut result = T::from(0);
+    let mut copies = VecDeque::<T>::new();
 
     for line in input {
-        let mut wins = Card::from(line).wins;
-        let card_count = 1 + copies.pop_front().unwrap_or(0);
+        let mut wins = matches(line);
+        let mut card_count = T::from(1);
+        if let Some(count) = copies.pop_front() {
+            card_count += &count;
+        }
 
         for i in 0..wins {
             if let Some(count) = copies.get_mut(i as usize) {
-                *count += card_count;
+                *count += &card_count;
                 wins -= 1;
             } else {
                 break;
@@ -61,19 +221,34 @@ pub fn solve_part_2<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
         }
 
         for _ in 0..wins {
-            copies.push_back(card_count);
+            copies.push_back(card_count.clone());
         }
 
-        result += card_count;
+        result += &card_count;
     }
 
     result
 }
 
+pub fn solve_part_2<'a>(input: impl Iterator<Item = &'a str>) -> u32 {
+    count_instances(input)
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use crate::*;
 
+    fn input() -> &'static str {
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+    }
+
     #[test]
     fn part_1() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
@@ -97,4 +272,134 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
         assert_eq!(solve_part_2(input.lines()), 30);
     }
+
+    #[test]
+    fn card() {
+        let card = Card::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        assert_eq!(card.wins(), 4);
+        assert_eq!(card.points(), 8);
+
+        let card = Card::from("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11");
+        assert_eq!(card.wins(), 0);
+        assert_eq!(card.points(), 0);
+    }
+
+    #[test]
+    fn scratchcard() {
+        let card = Scratchcard::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        assert_eq!(card.id, 1);
+        assert_eq!(card.winning, [41, 48, 83, 86, 17]);
+        assert_eq!(card.held, [83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(card.matches, 4);
+        assert_eq!(card.points(), 8);
+
+        let card = Scratchcard::from("Card  16: 31 18 13 56 72 | 74 77 10 23 35 67 36 11");
+        assert_eq!(card.id, 16);
+        assert_eq!(card.matches, 0);
+        assert_eq!(card.points(), 0);
+    }
+
+    /// Table of cards with numbers from a small range, so that most cards
+    /// have a few matches.
+    fn table() -> impl Strategy<Value = String> {
+        vec((vec(0..30u32, 5), vec(0..30u32, 8)), 0..40).prop_map(|cards| {
+            cards
+                .iter()
+                .enumerate()
+                .map(|(index, (winning, held))| {
+                    let numbers = |numbers: &[u32]| {
+                        numbers
+                            .iter()
+                            .map(|number| format!("{number:2}"))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    };
+                    format!(
+                        "Card {}: {} | {}",
+                        index + 1,
+                        numbers(winning),
+                        numbers(held)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn fast_paths_same_as_puzzle_scoring(input in table()) {
+            prop_assert_eq!(solve_part_1(input.lines()), solve_part_1_with(input.lines(), &Puzzle));
+            prop_assert_eq!(
+                count_instances::<u64>(input.lines()),
+                count_instances_with::<u64>(input.lines(), &Puzzle)
+            );
+        }
+    }
+
+    /// Table of `count` cards which all win copies of the next ten cards.
+    fn winning_table(count: u32) -> String {
+        let numbers = (1..=10)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        (1..=count)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn copies_beyond_u64() {
+        let input = winning_table(100);
+        let total = count_instances::<u128>(input.lines());
+
+        assert!(total > u64::MAX as u128);
+        let cards = input.lines().map(Scratchcard::from).collect::<Vec<_>>();
+        assert_eq!(Ledger::<u128>::new(&cards, &Puzzle).total(), total);
+        assert_eq!(
+            count_instances::<u64>(input.lines().take(40)) as u128,
+            count_instances::<u128>(input.lines().take(40))
+        );
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn copies_beyond_u128() {
+        let input = winning_table(300);
+        let total = count_instances::<BigUint>(input.lines());
+
+        assert!(total > BigUint::from(u128::MAX));
+        let cards = input.lines().map(Scratchcard::from).collect::<Vec<_>>();
+        assert_eq!(Ledger::<BigUint>::new(&cards, &Puzzle).total(), total);
+        assert_eq!(
+            count_instances::<BigUint>(input.lines().take(100)),
+            BigUint::from(count_instances::<u128>(input.lines().take(100)))
+        );
+    }
+
+    #[test]
+    fn ledger() {
+        let cards = input().lines().map(Scratchcard::from).collect::<Vec<_>>();
+        let ledger: Ledger = Ledger::new(&cards, &Puzzle);
+
+        assert_eq!(
+            ledger
+                .entries
+                .iter()
+                .map(|entry| entry.instances)
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 8, 14, 1]
+        );
+        assert_eq!(
+            ledger.entry(4).unwrap().sources,
+            [
+                Source { id: 1, copies: 1 },
+                Source { id: 2, copies: 2 },
+                Source { id: 3, copies: 4 },
+            ]
+        );
+        assert_eq!(ledger.entry(6).unwrap().sources, []);
+        assert_eq!(ledger.total(), solve_part_2(input().lines()));
+    }
 }
@@ -0,0 +1,224 @@
+//! Match counting without hashing.
+//!
+//! Card numbers are small, so a set of them fits in two words with one bit
+//! per number. Sets holding a number past the bitset fall back to hashing.
+
+use std::collections::HashSet;
+
+/// Set of card numbers, a bitset while all of them are below 128.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberSet {
+    Bits([u64; 2]),
+    Hashed(HashSet<u32>),
+}
+
+impl Default for NumberSet {
+    fn default() -> Self {
+        NumberSet::Bits([0; 2])
+    }
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, number: u32) {
+        match self {
+            NumberSet::Bits(bits) if number < 128 => {
+                bits[number as usize / 64] |= 1 << (number % 64);
+            }
+            NumberSet::Bits(_) => {
+                let mut hashed = self.iter().collect::<HashSet<_>>();
+                hashed.insert(number);
+                *self = NumberSet::Hashed(hashed);
+            }
+            NumberSet::Hashed(hashed) => {
+                hashed.insert(number);
+            }
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            NumberSet::Bits(bits) => {
+                number < 128 && bits[number as usize / 64] >> (number % 64) & 1 == 1
+            }
+            NumberSet::Hashed(hashed) => hashed.contains(&number),
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match self {
+            NumberSet::Bits(bits) => Box::new(
+                (0..128)
+                    .filter(move |number| bits[*number as usize / 64] >> (number % 64) & 1 == 1),
+            ),
+            NumberSet::Hashed(hashed) => Box::new(hashed.iter().copied()),
+        }
+    }
+
+    /// Number of numbers in both sets.
+    pub fn intersection_len(&self, other: &NumberSet) -> u32 {
+        match (self, other) {
+            (NumberSet::Bits(left), NumberSet::Bits(right)) => {
+                (left[0] & right[0]).count_ones() + (left[1] & right[1]).count_ones()
+            }
+            (NumberSet::Hashed(hashed), other) | (other, NumberSet::Hashed(hashed)) => hashed
+                .iter()
+                .filter(|number| other.contains(**number))
+                .count()
+                as u32,
+        }
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut set = NumberSet::default();
+        for number in iter {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+/// Numbers in the ASCII text `bytes`, split at whitespace and parsed like
+/// `str::parse::<u32>`, the way [`Scratchcard::from`](crate::Scratchcard)
+/// reads them: words that aren't numbers or are too large for a `u32` are
+/// skipped.
+fn numbers(bytes: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    bytes
+        // The ASCII characters `char::is_whitespace` accepts.
+        .split(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\x0B' | b'\x0C' | b'\r'))
+        .filter_map(|word| {
+            let digits = word.strip_prefix(b"+").unwrap_or(word);
+            if digits.is_empty() {
+                return None;
+            }
+            digits.iter().try_fold(0u32, |number, digit| {
+                if !digit.is_ascii_digit() {
+                    return None;
+                }
+                number.checked_mul(10)?.checked_add((digit - b'0') as u32)
+            })
+        })
+}
+
+/// Number of held numbers of the card `line` that are winning numbers, read
+/// straight from its bytes. Lines that aren't ASCII, which may hold other
+/// whitespace, go through [`Scratchcard::from`](crate::Scratchcard).
+pub fn matches(line: &str) -> u32 {
+    if !line.is_ascii() {
+        return crate::Scratchcard::from(line).matches;
+    }
+
+    let line = line.as_bytes();
+    let colon = line
+        .iter()
+        .position(|byte| *byte == b':')
+        .expect("failed to split at ':'");
+    let bar = colon
+        + line[colon..]
+            .iter()
+            .position(|byte| *byte == b'|')
+            .expect("failed to split numbers at '|'");
+
+    let winning = numbers(&line[colon + 1..bar]).collect::<NumberSet>();
+    let held = numbers(&line[bar + 1..]).collect::<NumberSet>();
+    winning.intersection_len(&held)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use super::*;
+
+    #[test]
+    fn bits_and_fallback() {
+        let mut set = [1, 64, 127].into_iter().collect::<NumberSet>();
+        assert!(matches!(set, NumberSet::Bits(_)));
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+        assert!(!set.contains(1000));
+
+        set.insert(1000);
+        assert!(matches!(set, NumberSet::Hashed(_)));
+        let mut numbers = set.iter().collect::<Vec<_>>();
+        numbers.sort_unstable();
+        assert_eq!(numbers, [1, 64, 127, 1000]);
+
+        let small = [0, 1, 64, 100].into_iter().collect::<NumberSet>();
+        assert_eq!(small.intersection_len(&small), 4);
+        assert_eq!(small.intersection_len(&set), 2);
+        assert_eq!(set.intersection_len(&small), 2);
+    }
+
+    #[test]
+    fn numbers_too_large() {
+        for (line, expected) in [
+            ("Card 1: 99999999999 5 | 5", 1),
+            ("Card 1: 4294967295 | 4294967295", 1),
+            // One past u32::MAX, skipped rather than read as 0.
+            ("Card 1: 4294967296 | 0", 0),
+        ] {
+            assert_eq!(matches(line), expected, "{line}");
+            assert_eq!(crate::Scratchcard::from(line).matches, expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn words_that_are_not_numbers() {
+        for (line, expected) in [
+            ("Card 1: 5,6 | 6", 0),
+            ("Card 1: 5 6 | 5,6", 0),
+            ("Card 1: +5 | 5", 1),
+            ("Card 1: -5 | 5", 0),
+            ("Card 1: + 5 | 5", 1),
+            ("Card 1: 5\t6\x0B7 | 7", 1),
+            ("Card 1: 5\u{a0}6 | 6", 1),
+            ("Card 1: 5é | 5", 0),
+        ] {
+            assert_eq!(matches(line), expected, "{line:?}");
+            assert_eq!(crate::Scratchcard::from(line).matches, expected, "{line:?}");
+        }
+    }
+
+    /// Card line with numbers below one of a few bounds, so that some cards
+    /// fit the bitset and others fall back to hashing.
+    fn line() -> impl Strategy<Value = String> {
+        let bound = select(vec![20u32, 100, 128, 129, 1000]);
+        (0..300u32, bound)
+            .prop_flat_map(|(id, bound)| (Just(id), vec(0..bound, 5), vec(0..bound, 8)))
+            .prop_map(|(id, winning, held)| {
+                let numbers = |numbers: Vec<u32>| {
+                    numbers
+                        .iter()
+                        .map(|number| format!("{number:2}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                format!("Card {id}: {} | {}", numbers(winning), numbers(held))
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn same_as_hash_sets(line in line()) {
+            let card = crate::Scratchcard::from(line.as_str());
+            let winning = card.winning.iter().collect::<HashSet<_>>();
+            let held = card.held.iter().collect::<HashSet<_>>();
+
+            let expected = winning.intersection(&held).count() as u32;
+
+            prop_assert_eq!(matches(&line), expected);
+            prop_assert_eq!(card.matches, expected);
+        }
+
+        #[test]
+        fn same_as_scratchcard(
+            winning in "[0-9 +,\t\x0B-]{0,20}",
+            held in "[0-9 +,\t\x0B-]{0,20}",
+        ) {
+            let line = format!("Card 1:{winning}|{held}");
+            prop_assert_eq!(matches(&line), crate::Scratchcard::from(line.as_str()).matches);
+        }
+    }
+}
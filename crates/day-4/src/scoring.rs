@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::{doubling, Scratchcard};
+
+/// Rules for what a scratchcard is worth. The provided methods are the
+/// puzzle's rules, part 1 for [`Scoring::points`] and part 2 for
+/// [`Scoring::won_cards`].
+pub trait Scoring {
+    /// Points `card` is worth: one for the first match, doubled for every
+    /// further one.
+    fn points(&self, card: &Scratchcard) -> u32 {
+        doubling(card.matches)
+    }
+
+    /// Positions of the cards in a table of `count` cards one instance of the
+    /// card at `index` wins a copy of: one of each following card for every
+    /// match.
+    fn won_cards(&self, card: &Scratchcard, index: usize, count: usize) -> Vec<usize> {
+        (index + 1..count).take(card.matches as usize).collect()
+    }
+}
+
+/// The puzzle's rules.
+pub struct Puzzle;
+
+impl Scoring for Puzzle {}
+
+/// One point for every match.
+pub struct Linear;
+
+impl Scoring for Linear {
+    fn points(&self, card: &Scratchcard) -> u32 {
+        card.matches
+    }
+}
+
+/// Copies of at most this many following cards.
+pub struct Capped(pub u32);
+
+impl Scoring for Capped {
+    fn won_cards(&self, card: &Scratchcard, index: usize, count: usize) -> Vec<usize> {
+        (index + 1..count)
+            .take(card.matches.min(self.0) as usize)
+            .collect()
+    }
+}
+
+/// Copies continue from the start of the table past its end, never reaching
+/// back to the card itself.
+pub struct WrapAround;
+
+impl Scoring for WrapAround {
+    fn won_cards(&self, card: &Scratchcard, index: usize, count: usize) -> Vec<usize> {
+        (1..count)
+            .take(card.matches as usize)
+            .map(|offset| (index + offset) % count)
+            .collect()
+    }
+}
+
+/// Points summing the weights of the matching numbers, a number without a
+/// weight counting one.
+pub struct Weighted(pub HashMap<u32, u32>);
+
+impl Scoring for Weighted {
+    fn points(&self, card: &Scratchcard) -> u32 {
+        card.matching()
+            .map(|number| self.0.get(&number).copied().unwrap_or(1))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_instances_with, solve_part_1_with, Ledger, Source};
+
+    fn input() -> &'static str {
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+    }
+
+    fn total(scoring: &impl Scoring) -> u32 {
+        count_instances_with(input().lines(), scoring)
+    }
+
+    fn points(scoring: &impl Scoring) -> u32 {
+        solve_part_1_with(input().lines(), scoring)
+    }
+
+    #[test]
+    fn built_in_points() {
+        assert_eq!(points(&Puzzle), 13);
+        assert_eq!(points(&Linear), 4 + 2 + 2 + 1);
+        assert_eq!(points(&Weighted(HashMap::from([(83, 10), (61, 0)]))), 17);
+    }
+
+    #[test]
+    fn built_in_copies() {
+        assert_eq!(total(&Puzzle), 30);
+        assert_eq!(total(&WrapAround), 30);
+        assert_eq!(total(&Capped(4)), 30);
+        assert_eq!(total(&Capped(1)), 1 + 2 + 3 + 4 + 5 + 1);
+        assert_eq!(total(&Capped(0)), 6);
+    }
+
+    #[test]
+    fn wrap_around() {
+        let cards = "Card 1: 5 | 6
+Card 2: 1 2 3 | 1 2 3
+Card 3: 1 2 | 1 2"
+            .lines()
+            .map(Scratchcard::from)
+            .collect::<Vec<_>>();
+        let ledger: Ledger = Ledger::new(&cards, &WrapAround);
+
+        // Card 2 wins card 3 and, wrapping, card 1. Card 3 wins cards 1 and 2,
+        // which have already been played, so those copies win nothing.
+        assert_eq!(
+            ledger
+                .entries
+                .iter()
+                .map(|entry| entry.instances)
+                .collect::<Vec<_>>(),
+            [1 + 1 + 2, 1 + 2, 1 + 1]
+        );
+        assert_eq!(
+            ledger.entry(1).unwrap().sources,
+            [Source { id: 2, copies: 1 }, Source { id: 3, copies: 2 },]
+        );
+    }
+}
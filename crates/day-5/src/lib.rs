@@ -1,13 +1,20 @@
 use std::marker::PhantomData;
 
-trait FromStrToVec
+use intervals::IntervalSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub trait FromStrToVec
 where
     Self: Sized,
 {
     fn to_vec(value: &str) -> Vec<Self>;
 }
 
-struct SingleSeed(u64);
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SingleSeed(pub u64);
 
 impl From<&str> for SingleSeed {
     fn from(value: &str) -> Self {
@@ -25,7 +32,9 @@ impl FromStrToVec for SingleSeed {
     }
 }
 
-struct SeedRange(std::ops::Range<u64>);
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SeedRange(pub std::ops::Range<u64>);
 
 impl FromStrToVec for SeedRange {
     fn to_vec(value: &str) -> Vec<Self> {
@@ -45,9 +54,11 @@ impl FromStrToVec for SeedRange {
 }
 
 #[derive(Debug)]
-struct Range<T> {
-    destination: std::ops::Range<u64>,
-    source: std::ops::Range<u64>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Range<T> {
+    pub destination: std::ops::Range<u64>,
+    pub source: std::ops::Range<u64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _marker: PhantomData<T>,
 }
 
@@ -63,44 +74,15 @@ impl Range<SingleSeed> {
 }
 
 impl Range<SeedRange> {
-    /// Returns destination range if found and ranges that did not fit.
-    fn destination(
-        &self,
-        source: std::ops::Range<u64>,
-    ) -> (Option<std::ops::Range<u64>>, Vec<std::ops::Range<u64>>) {
-        if !self.source.contains(&source.start) && !self.source.contains(&(source.end)) {
-            return (None, vec![source]);
-        }
-
-        let mut leftovers = Vec::new();
-
-        let mut offest = 0;
-        if source.start < self.source.start {
-            leftovers.push(std::ops::Range {
-                start: source.start,
-                end: self.source.start,
+    /// Moves `sources` the way this range moves its source.
+    fn destinations(&self, sources: &IntervalSet<u64>) -> IntervalSet<u64> {
+        sources
+            .iter()
+            .map(|source| {
+                source.start - self.source.start + self.destination.start
+                    ..source.end - self.source.start + self.destination.start
             })
-        } else {
-            offest = source.start - self.source.start;
-        }
-
-        let length = if source.end > self.source.end {
-            leftovers.push(std::ops::Range {
-                start: self.source.end,
-                end: source.end,
-            });
-            self.source.end - self.source.start - offest
-        } else {
-            source.end - self.source.start - offest
-        };
-
-        (
-            Some(std::ops::Range {
-                start: self.destination.start + offest,
-                end: self.destination.start + offest + length,
-            }),
-            leftovers,
-        )
+            .collect()
     }
 }
 
@@ -142,8 +124,10 @@ impl<T> From<&str> for Range<T> {
 }
 
 #[derive(Debug)]
-struct Map<T> {
-    ranges: Vec<Range<T>>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Map<T> {
+    pub ranges: Vec<Range<T>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _marker: PhantomData<T>,
 }
 
@@ -177,36 +161,27 @@ impl Map<SingleSeed> {
 }
 
 impl Map<SeedRange> {
-    fn destinations(&self, source: std::ops::Range<u64>) -> Vec<std::ops::Range<u64>> {
-        let mut sources = vec![source];
-        let mut destinations = Vec::new();
+    /// Destinations of `sources`. Values no range covers keep their number,
+    /// and values several ranges cover are moved by the first of them.
+    fn destinations(&self, sources: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut unmapped = sources.clone();
+        let mut destinations = IntervalSet::new();
 
         for range in &self.ranges {
-            let mut leftovers = Vec::new();
-
-            while let Some(source) = sources.pop() {
-                {
-                    let (dest, mut lo) = range.destination(source);
-                    leftovers.append(&mut lo);
-
-                    if let Some(dest) = dest {
-                        destinations.push(dest);
-                    }
-                }
-            }
-
-            sources.append(&mut leftovers);
+            let source = IntervalSet::from(range.source.clone());
+            destinations = destinations.union(&range.destinations(&unmapped.intersection(&source)));
+            unmapped = unmapped.difference(&source);
         }
 
-        destinations.append(&mut sources);
-        destinations
+        destinations.union(&unmapped)
     }
 }
 
 #[derive(Debug)]
-struct Almanac<T> {
-    seeds: Vec<T>,
-    maps: Vec<Map<T>>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Almanac<T> {
+    pub seeds: Vec<T>,
+    pub maps: Vec<Map<T>>,
 }
 
 impl<'a, T, K> From<T> for Almanac<K>
@@ -236,7 +211,7 @@ where
 }
 
 impl Almanac<SingleSeed> {
-    fn find_lowest_location(&self) -> u64 {
+    pub fn find_lowest_location(&self) -> u64 {
         let mut location = u64::MAX;
 
         for seed in &self.seeds {
@@ -256,30 +231,18 @@ impl Almanac<SingleSeed> {
 }
 
 impl Almanac<SeedRange> {
-    fn find_lowest_location(self) -> u64 {
-        let mut location = u64::MAX;
-
-        for range in self.seeds {
-            let mut ranges = vec![range.0];
-
-            for map in &self.maps {
-                let mut destinations = Vec::new();
-
-                while let Some(range) = ranges.pop() {
-                    destinations.append(&mut map.destinations(range));
-                }
-
-                ranges.append(&mut destinations);
-            }
-
-            if let Some(lowest_range) = ranges.iter().min_by(|l, r| l.start.cmp(&r.start)) {
-                if lowest_range.start < location {
-                    location = lowest_range.start
-                }
-            }
+    pub fn find_lowest_location(self) -> u64 {
+        let mut locations = self
+            .seeds
+            .into_iter()
+            .map(|range| range.0)
+            .collect::<IntervalSet<_>>();
+
+        for map in &self.maps {
+            locations = map.destinations(&locations);
         }
 
-        location
+        locations.min().unwrap_or(u64::MAX)
     }
 }
 
@@ -305,6 +268,60 @@ mod tests {
         assert_eq!(solve_part_2(input()), 46);
     }
 
+    /// Lowest location of the seeds in the ranges, found one seed at a time.
+    fn lowest_location_by_seed(input: &str) -> u64 {
+        let ranges = Almanac::<SeedRange>::from(input.split("\n\n")).seeds;
+        let almanac = Almanac::<SingleSeed>::from(input.split("\n\n"));
+
+        ranges
+            .iter()
+            .flat_map(|range| range.0.clone())
+            .map(|seed| {
+                almanac
+                    .maps
+                    .iter()
+                    .fold(seed, |target, map| map.destination(target))
+            })
+            .min()
+            .unwrap_or(u64::MAX)
+    }
+
+    #[test]
+    fn part_2_by_seed() {
+        assert_eq!(lowest_location_by_seed(input()), 46);
+
+        // Seed ranges covering a whole map range, starting or ending inside
+        // one, and spanning overlapping ranges.
+        for input in [
+            "seeds: 0 20\n\nmap:\n100 5 3",
+            "seeds: 4 10\n\nmap:\n0 5 3",
+            "seeds: 6 10\n\nmap:\n100 5 3\n50 12 10",
+            "seeds: 3 4\n\nmap:\n100 5 3\n0 4 4",
+            "seeds: 10 10\n\na:\n0 12 3\n\nb:\n30 0 5\n1 14 2",
+        ] {
+            assert_eq!(
+                solve_part_2(input),
+                lowest_location_by_seed(input),
+                "{input}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let almanac = Almanac::<SeedRange>::from(input().split("\n\n"));
+        let json = serde_json::to_string(&almanac).unwrap();
+        let almanac: Almanac<SeedRange> = serde_json::from_str(&json).unwrap();
+        assert_eq!(almanac.find_lowest_location(), 46);
+
+        let almanac: Almanac<SingleSeed> = serde_json::from_str(
+            r#"{"seeds":[1,5],"maps":[{"ranges":[{"destination":{"start":10,"end":12},"source":{"start":0,"end":2}}]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(almanac.find_lowest_location(), 5);
+    }
+
     fn input() -> &'static str {
         "seeds: 79 14 55 13
 
@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn races(c: &mut Criterion) {
+    let mut group = c.benchmark_group("races");
+    for (name, time, distance) in [
+        ("part 1", 30, 200),
+        ("part 2", 71530, 940200),
+        ("puzzle-sized", 48_989_083, 390_110_311_121_360),
+    ] {
+        assert_eq!(
+            day_6::ways_to_win(time, distance),
+            day_6::ways_to_win_closed_form(time, distance)
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("loop", name),
+            &(time, distance),
+            |b, race| b.iter(|| day_6::ways_to_win(race.0, race.1)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("closed form", name),
+            &(time, distance),
+            |b, race| b.iter(|| day_6::ways_to_win_closed_form(race.0, race.1)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, races);
+criterion_main!(benches);
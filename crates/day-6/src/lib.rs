@@ -1,4 +1,6 @@
-fn solve(time: u64, distance: u64) -> u64 {
+/// Number of ways to beat `distance` in a race of `time`, counted by stepping
+/// down from the best hold time. Panics if the race can't be won.
+pub fn ways_to_win(time: u64, distance: u64) -> u64 {
     let mut result = 0;
     let mut mid_point = time / 2;
 
@@ -7,7 +9,7 @@ fn solve(time: u64, distance: u64) -> u64 {
         result += 1;
     }
 
-    if time % 2 == 0 {
+    if time.is_multiple_of(2) {
         result = (result * 2) - 1;
     } else {
         result *= 2;
@@ -16,6 +18,33 @@ fn solve(time: u64, distance: u64) -> u64 {
     result
 }
 
+/// Number of ways to beat `distance` in a race of `time`, from the roots of
+/// `hold * (time - hold) = distance`. The floating-point root is only a first
+/// guess, corrected with exact integer checks.
+pub fn ways_to_win_closed_form(time: u64, distance: u64) -> u64 {
+    let wins = |hold: u64| hold as u128 * (time - hold) as u128 > distance as u128;
+
+    let discriminant = (time as f64).powi(2) - 4.0 * distance as f64;
+    if discriminant < 0.0 {
+        return 0;
+    }
+
+    let mut shortest = ((time as f64 - discriminant.sqrt()) / 2.0).max(0.0) as u64;
+    shortest = shortest.min(time / 2);
+    while shortest > 0 && wins(shortest - 1) {
+        shortest -= 1;
+    }
+    while shortest <= time / 2 && !wins(shortest) {
+        shortest += 1;
+    }
+
+    if shortest > time / 2 {
+        0
+    } else {
+        time - 2 * shortest + 1
+    }
+}
+
 pub fn parse_times_and_distances(input: &str) -> (Vec<u64>, Vec<u64>) {
     let mut lines = input.lines();
 
@@ -44,14 +73,15 @@ pub fn solve_part_1(input: &str) -> u64 {
     let (times, distances) = parse_times_and_distances(input);
 
     let mut result = 1;
-    for (time, distance) in times.into_iter().zip(distances.into_iter()) {
-        result *= solve(time, distance);
+    for (time, distance) in times.into_iter().zip(distances) {
+        result *= ways_to_win(time, distance);
     }
 
     result
 }
 
-pub fn solve_part_2(input: &str) -> u64 {
+/// Reads the single race of part 2, ignoring the spaces between digits.
+pub fn parse_time_and_distance(input: &str) -> (u64, u64) {
     let mut lines = input.lines();
 
     let line = lines.next().unwrap();
@@ -74,11 +104,34 @@ pub fn solve_part_2(input: &str) -> u64 {
         .parse::<u64>()
         .unwrap();
 
-    solve(time, distance)
+    (time, distance)
+}
+
+pub fn solve_part_2(input: &str) -> u64 {
+    let (time, distance) = parse_time_and_distance(input);
+    ways_to_win(time, distance)
+}
+
+pub fn solve_part_2_closed_form(input: &str) -> u64 {
+    let (time, distance) = parse_time_and_distance(input);
+    ways_to_win_closed_form(time, distance)
+}
+
+/// A named way of solving a part of the puzzle.
+pub type Solver = (&'static str, fn(&str) -> u64);
+
+/// Every way of solving part 2, by name.
+pub fn strategies() -> &'static [Solver] {
+    &[
+        ("loop", solve_part_2),
+        ("closed form", solve_part_2_closed_form),
+    ]
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -91,6 +144,44 @@ mod tests {
         assert_eq!(solve_part_2(input()), 71503);
     }
 
+    #[test]
+    fn strategies_agree() {
+        for (input, expected) in [
+            (input(), 71503),
+            ("Time: 71530\nDistance: 940200", 71503),
+            ("Time: 4898 9083\nDistance: 390 1103 1112 1360", 28_973_936),
+        ] {
+            for (name, solve) in strategies() {
+                assert_eq!(solve(input), expected, "{name}: {input}");
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn closed_form_same_as_loop(
+            (time, distance) in (2..100_000u64).prop_flat_map(|time| (Just(time), 0..time * time / 4))
+        ) {
+            prop_assert_eq!(ways_to_win_closed_form(time, distance), ways_to_win(time, distance));
+        }
+    }
+
+    #[test]
+    fn closed_form() {
+        assert_eq!(ways_to_win_closed_form(7, 9), 4);
+        assert_eq!(ways_to_win_closed_form(30, 200), 9);
+        assert_eq!(ways_to_win_closed_form(71530, 940200), 71503);
+        // The best hold time only ties or loses.
+        assert_eq!(ways_to_win_closed_form(30, 225), 0);
+        assert_eq!(ways_to_win_closed_form(7, 12), 0);
+        assert_eq!(ways_to_win_closed_form(0, 0), 0);
+        assert_eq!(ways_to_win_closed_form(1, 0), 0);
+        assert_eq!(
+            ways_to_win_closed_form(u32::MAX as u64, 1),
+            ways_to_win(u32::MAX as u64, 1)
+        );
+    }
+
     fn input() -> &'static str {
         "Time:      7  15   30
 Distance:  9  40  200"
@@ -3,8 +3,12 @@ use std::{
     collections::{HashMap, VecDeque},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-enum CardKind {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CardKind {
     Two,
     Three,
     Four,
@@ -21,7 +25,8 @@ enum CardKind {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-enum CardKindWildCard {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CardKindWildCard {
     Jack,
     Two,
     Three,
@@ -66,7 +71,8 @@ impl_card_kind_from!(CardKind);
 impl_card_kind_from!(CardKindWildCard);
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum HandKind {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HandKind {
     HighCard,
     OnePair,
     TwoPair,
@@ -103,10 +109,11 @@ impl From<&[u32]> for HandKind {
 }
 
 #[derive(Debug)]
-struct Hand<T> {
-    bid: u64,
-    kind: HandKind,
-    held: [T; 5],
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hand<T> {
+    pub bid: u64,
+    pub kind: HandKind,
+    pub held: [T; 5],
 }
 
 impl From<&str> for Hand<CardKind> {
@@ -182,18 +189,12 @@ impl From<&str> for Hand<CardKindWildCard> {
     }
 }
 
-fn solve<'a, T>(input: &'a str) -> u64
+pub fn total_winnings<T>(mut hands: Vec<Hand<T>>) -> u64
 where
     T: PartialEq + PartialOrd + Ord,
-    Hand<T>: From<&'a str>,
 {
     let mut result = 0;
 
-    let mut hands: Vec<Hand<T>> = Vec::new();
-    for line in input.lines() {
-        hands.push(line.into());
-    }
-
     hands.sort_by(|l, r| {
         let mut ordering = l.kind.cmp(&r.kind);
         if ordering == Ordering::Equal {
@@ -214,6 +215,19 @@ where
     result
 }
 
+fn solve<'a, T>(input: &'a str) -> u64
+where
+    T: PartialEq + PartialOrd + Ord,
+    Hand<T>: From<&'a str>,
+{
+    let mut hands: Vec<Hand<T>> = Vec::new();
+    for line in input.lines() {
+        hands.push(line.into());
+    }
+
+    total_winnings(hands)
+}
+
 pub fn solve_part_1(input: &str) -> u64 {
     solve::<CardKind>(input)
 }
@@ -245,6 +259,24 @@ mod tests {
         assert_eq!(solve_part_2(input()), 5905)
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let hands = input()
+            .lines()
+            .map(Hand::<CardKindWildCard>::from)
+            .collect::<Vec<_>>();
+        let json = serde_json::to_string(&hands).unwrap();
+        let hands: Vec<Hand<CardKindWildCard>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(total_winnings(hands), 5905);
+
+        let hand: Hand<CardKind> = serde_json::from_str(
+            r#"{"bid":7,"kind":"FullHouse","held":["Two","Two","Ace","Ace","Ace"]}"#,
+        )
+        .unwrap();
+        assert_eq!(total_winnings(vec![hand]), 7);
+    }
+
     fn input() -> &'static str {
         "32T3K 765
 T55J5 684
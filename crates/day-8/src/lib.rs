@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug)]
-struct Instructions<'a> {
-    str: &'a str,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Instructions<'a> {
+    pub str: &'a str,
 }
 
 impl<'a> From<&'a str> for Instructions<'a> {
@@ -12,9 +16,10 @@ impl<'a> From<&'a str> for Instructions<'a> {
 }
 
 #[derive(Debug)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Node<'a> {
+    pub left: &'a str,
+    pub right: &'a str,
 }
 
 impl<'a> From<&'a str> for Node<'a> {
@@ -30,13 +35,16 @@ impl<'a> From<&'a str> for Node<'a> {
 }
 
 #[derive(Debug)]
-struct Graph<'a> {
-    map: HashMap<&'a str, Node<'a>>,
-    starts: Vec<&'a str>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Graph<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub map: HashMap<&'a str, Node<'a>>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub starts: Vec<&'a str>,
 }
 
 impl Graph<'_> {
-    fn steps(&self, instructions: &Instructions) -> u32 {
+    pub fn steps(&self, instructions: &Instructions) -> u32 {
         let mut steps = 0;
         let mut key = "AAA";
 
@@ -60,7 +68,7 @@ impl Graph<'_> {
         steps
     }
 
-    fn ghost_steps(mut self, instructions: &Instructions) -> u64 {
+    pub fn ghost_steps(mut self, instructions: &Instructions) -> u64 {
         let mut steps = Vec::new();
 
         for key in &mut self.starts {
@@ -168,6 +176,22 @@ mod tests {
         assert_eq!(solve_part_2(input_ghost()), 6);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let (instructions, graph) = input_ghost().split_once("\n\n").unwrap();
+        let json =
+            serde_json::to_string(&(Instructions::from(instructions), Graph::from(graph))).unwrap();
+        let (instructions, graph): (Instructions, Graph) = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph.ghost_steps(&instructions), 6);
+
+        let graph: Graph = serde_json::from_str(
+            r#"{"map":{"AAA":{"left":"ZZZ","right":"AAA"},"ZZZ":{"left":"ZZZ","right":"ZZZ"}},"starts":["AAA"]}"#,
+        )
+        .unwrap();
+        assert_eq!(graph.steps(&Instructions::from("L")), 1);
+    }
+
     fn input() -> &'static str {
         "RL
 
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-struct Sequence {
+/// A sequence of readings from the OASIS report.
+pub struct Sequence {
     seq: Vec<i32>,
     cache: HashMap<(usize, usize), i32>,
 }
@@ -21,8 +22,27 @@ impl From<&str> for Sequence {
 }
 
 impl Sequence {
+    /// Readings in the order they were reported.
+    pub fn values(&self) -> impl Iterator<Item = i32> + '_ {
+        self.seq.iter().rev().copied()
+    }
+
+    /// Extrapolates the reading that follows the last one.
+    pub fn next_value(&mut self) -> i32 {
+        self.next()
+    }
+
+    /// Extrapolates the reading that precedes the first one.
+    pub fn previous_value(&mut self) -> i32 {
+        self.rev();
+        let result = self.next();
+        self.rev();
+        result
+    }
+
     fn rev(&mut self) {
-        self.seq.reverse()
+        self.seq.reverse();
+        self.cache.clear();
     }
 
     fn next(&mut self) -> i32 {
@@ -74,7 +94,7 @@ impl Sequence {
 pub fn solve_part_1(input: &str) -> i32 {
     let mut result = 0;
     for line in input.lines() {
-        result += Sequence::from(line).next();
+        result += Sequence::from(line).next_value();
     }
 
     result
@@ -83,9 +103,7 @@ pub fn solve_part_1(input: &str) -> i32 {
 pub fn solve_part_2(input: &str) -> i32 {
     let mut result = 0;
     for line in input.lines() {
-        let mut seq = Sequence::from(line);
-        seq.rev();
-        result += seq.next();
+        result += Sequence::from(line).previous_value();
     }
 
     result
@@ -110,6 +128,16 @@ mod tests {
         assert_eq!(solve_part_1("10 9 2 -15 -46 -95 -166 -263 -390 -551 -750 -991 -1278 -1615 -2006 -2455 -2966 -3543 -4190 -4911 -5710"), -6591);
     }
 
+    #[test]
+    fn sequence() {
+        let mut seq = Sequence::from("10 13 16 21 30 45");
+
+        assert_eq!(seq.values().collect::<Vec<_>>(), [10, 13, 16, 21, 30, 45]);
+        assert_eq!(seq.next_value(), 68);
+        assert_eq!(seq.previous_value(), 5);
+        assert_eq!(seq.next_value(), 68);
+    }
+
     fn input() -> &'static str {
         "0 3 6 9 12 15
 1 3 6 10 15 21
@@ -0,0 +1,276 @@
+use std::ops::Range;
+
+/// Set of values stored as half-open intervals.
+///
+/// The intervals are kept normalized: sorted, non-empty, and neither
+/// overlapping nor touching, so two sets with the same values have the same
+/// intervals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts `ranges` and merges the ones that overlap or touch.
+    fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut normalized: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match normalized.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => normalized.push(range),
+            }
+        }
+
+        Self { ranges: normalized }
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalize(ranges);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= *value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(value))
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Intervals of the set in ascending order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut left, mut right) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let start = l.start.max(r.start);
+            let end = l.end.min(r.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if l.end < r.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            while let Some(other) = others.next_if(|other| other.end <= range.end) {
+                if start < other.start {
+                    ranges.push(start..other.start);
+                }
+                start = start.max(other.end);
+            }
+            if let Some(other) = others.peek() {
+                if other.start < range.end {
+                    if start < other.start {
+                        ranges.push(start..other.start);
+                    }
+                    start = range.end;
+                }
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T: Ord + Copy> From<Range<T>> for IntervalSet<T> {
+    fn from(value: Range<T>) -> Self {
+        Self::normalize(vec![value])
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<T: Ord + Copy> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.extend(iter);
+        *self = Self::normalize(ranges);
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points `0..UNIVERSE` the tests enumerate every subset of.
+    const UNIVERSE: u8 = 7;
+
+    /// Set of points as a bitmask, the model the interval sets are checked
+    /// against.
+    fn points(set: &IntervalSet<u8>) -> u32 {
+        set.iter()
+            .flat_map(Clone::clone)
+            .map(|point| 1 << point)
+            .sum()
+    }
+
+    /// Interval set with the points of `mask`, built one point at a time.
+    fn from_mask(mask: u32) -> IntervalSet<u8> {
+        (0..UNIVERSE)
+            .filter(|point| mask >> point & 1 == 1)
+            .map(|point| point..point + 1)
+            .collect()
+    }
+
+    fn assert_normalized(set: &IntervalSet<u8>) {
+        assert!(set.iter().all(|range| range.start < range.end), "{set:?}");
+        assert!(
+            set.ranges.windows(2).all(|w| w[0].end < w[1].start),
+            "{set:?}"
+        );
+    }
+
+    /// Every range within `0..=UNIVERSE`, empty ones included.
+    fn ranges() -> Vec<Range<u8>> {
+        (0..=UNIVERSE)
+            .flat_map(|start| (0..=UNIVERSE).map(move |end| start..end))
+            .collect()
+    }
+
+    #[test]
+    fn normalization() {
+        for mask in 0..1 << UNIVERSE {
+            let set = from_mask(mask);
+            assert_normalized(&set);
+            assert_eq!(points(&set), mask);
+        }
+
+        let ranges = ranges();
+        for a in &ranges {
+            for b in &ranges {
+                for c in [0..0, 2..3, 5..7] {
+                    let set = [a.clone(), b.clone(), c.clone()]
+                        .into_iter()
+                        .collect::<IntervalSet<_>>();
+                    let expected = a
+                        .clone()
+                        .chain(b.clone())
+                        .chain(c)
+                        .map(|p| 1 << p)
+                        .fold(0, |m, p| m | p);
+
+                    assert_normalized(&set);
+                    assert_eq!(points(&set), expected, "{a:?} {b:?}");
+                }
+
+                let mut inserted = IntervalSet::from(a.clone());
+                inserted.insert(b.clone());
+                assert_eq!(inserted, IntervalSet::from_iter([a.clone(), b.clone()]));
+            }
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        for left in 0..1 << UNIVERSE {
+            for right in 0..1 << UNIVERSE {
+                let (l, r) = (from_mask(left), from_mask(right));
+
+                let union = l.union(&r);
+                let intersection = l.intersection(&r);
+                let difference = l.difference(&r);
+
+                for set in [&union, &intersection, &difference] {
+                    assert_normalized(set);
+                }
+                assert_eq!(points(&union), left | right);
+                assert_eq!(points(&intersection), left & right);
+                assert_eq!(points(&difference), left & !right);
+            }
+        }
+    }
+
+    #[test]
+    fn queries() {
+        for mask in 0..1 << UNIVERSE {
+            let set = from_mask(mask);
+
+            assert_eq!(set.is_empty(), mask == 0);
+            assert_eq!(set.min(), (mask != 0).then(|| mask.trailing_zeros() as u8));
+            for point in 0..=UNIVERSE + 1 {
+                assert_eq!(
+                    set.contains(&point),
+                    mask >> point & 1 == 1,
+                    "{set:?} {point}"
+                );
+            }
+            assert_eq!(
+                set.clone().into_iter().collect::<Vec<_>>(),
+                set.iter().cloned().collect::<Vec<_>>()
+            );
+        }
+    }
+}
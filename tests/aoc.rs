@@ -11,7 +11,7 @@ fn day_1_part_1() {
 #[test]
 fn day_1_part_2() {
     let input = read_to_string("input/day-1").expect("file present");
-    let result = day_1::solve_part_2(&input);
+    let result = day_1::solve_part_2(&input, &day_1::Vocabulary::english());
 
     assert_eq!(result, 53348);
 }
@@ -19,7 +19,8 @@ fn day_1_part_2() {
 #[test]
 fn day_2_part_1() {
     let input = read_to_string("input/day-2").expect("file present");
-    let result = day_2::solve_part_1(&input);
+    let settings = day_2::GameSettings::from("12 red, 13 green, 14 blue");
+    let result = day_2::solve_part_1(&input, &settings);
 
     assert_eq!(result, 2771);
 }